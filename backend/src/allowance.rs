@@ -0,0 +1,44 @@
+//! Allowance of a wallet
+
+use exonum::crypto::{self, Hash, HashStream};
+
+use super::proto;
+
+/// Amount of currency which the spender's wallet may transfer from the owner's wallet.
+#[derive(Clone, Debug, PartialEq, ProtobufConvert)]
+#[exonum(pb = "proto::Allowance", serde_pb_convert)]
+pub struct Allowance {
+    /// Name of the owner's wallet.
+    pub owner: String,
+    /// Name of the spender's wallet.
+    pub spender: String,
+    /// Remaining amount of currency.
+    pub amount: u64,
+}
+
+impl Allowance {
+    /// Create new allowance.
+    pub fn new(owner: &str, spender: &str, amount: u64) -> Self {
+        Self {
+            owner: owner.to_owned(),
+            spender: spender.to_owned(),
+            amount,
+        }
+    }
+    /// Returns a copy of this allowance with the amount decreased by the transferred amount.
+    pub fn spend(self, amount: u64) -> Self {
+        Self {
+            amount: self.amount - amount,
+            ..self
+        }
+    }
+}
+
+/// Returns the key of the allowance in the database, built from the hashes of the owner's
+/// and the spender's wallet names.
+pub fn allowance_key(owner: &str, spender: &str) -> Hash {
+    HashStream::new()
+        .update(crypto::hash(owner.as_bytes()).as_ref())
+        .update(crypto::hash(spender.as_bytes()).as_ref())
+        .hash()
+}
@@ -16,15 +16,21 @@
 
 use exonum::{
     api::{self, ServiceApiBuilder, ServiceApiState},
-    blockchain::{self, BlockProof, TransactionMessage},
-    crypto::{Hash, hash},
+    blockchain::{self, BlockProof, TransactionMessage, TransactionSet},
+    crypto::{Hash, PublicKey, hash},
 //    crypto::{Hash, PublicKey},
     explorer::BlockchainExplorer,
     helpers::Height,
-    storage::{ListProof, MapProof},
+    storage::{ListProof, MapProof, Snapshot},
 };
 
-use crate::{wallet::Wallet, Schema, CRYPTOCURRENCY_SERVICE_ID};
+use crate::{
+    allowance::{allowance_key, Allowance},
+    asset::{asset_balance_key, Asset},
+    htlc::Htlc, invoice::Invoice, transactions::{required_quorum, WalletTransactions},
+    transferproposal::TransferProposal, wallet::Wallet, walletevent::WalletEvent, Schema,
+    schema, CRYPTOCURRENCY_SERVICE_ID,
+};
 
 /// Describes the query parameters for the `get_wallet` endpoint.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -49,6 +55,21 @@ pub struct WalletHistory {
     pub proof: ListProof<Hash>,
     /// List of above transactions.
     pub transactions: Vec<TransactionMessage>,
+    /// Memos of the above transactions, empty for transactions without a memo.
+    pub memos: Vec<String>,
+    /// Fees paid for the above transactions.
+    pub fees: Vec<u64>,
+    /// Proof of the history records which are events of the service rather than transactions.
+    pub events: WalletEventsProof,
+}
+
+/// Proof of existence for the events recorded in a wallet history.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WalletEventsProof {
+    /// Proof of the whole database table.
+    pub to_table: MapProof<Hash, Hash>,
+    /// Proof of the events in this table.
+    pub to_events: MapProof<Hash, WalletEvent>,
 }
 
 /// Wallet information.
@@ -62,6 +83,357 @@ pub struct WalletInfo {
     pub wallet_history: Option<WalletHistory>,
 }
 
+/// Describes the query parameters for the `get_wallets_by_key` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct KeyQuery {
+    /// Public key of the owner.
+    pub pub_key: PublicKey,
+}
+
+/// Proof of existence for the names of the wallets owned by a public key.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KeyWalletsProof {
+    /// Proof of the whole database table.
+    pub to_table: MapProof<Hash, Hash>,
+    /// Proof of the hash of the wallet names in this table.
+    pub to_names: MapProof<PublicKey, Hash>,
+    /// Proof of the list of wallet names, if the key owns any wallets.
+    pub names: Option<ListProof<String>>,
+}
+
+/// Wallets owned by a public key.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KeyWalletsInfo {
+    /// Proof of the last block.
+    pub block_proof: BlockProof,
+    /// Proof of the names of the wallets.
+    pub key_wallets_proof: KeyWalletsProof,
+    /// Proof of the wallets themselves.
+    pub wallet_proof: WalletProof,
+}
+
+/// Describes the query parameters for the `get_proposal` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProposalQuery {
+    /// Id of the queried transfer proposal.
+    pub id: Hash,
+}
+
+/// Proof of existence for specific transfer proposal.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProposalProof {
+    /// Proof of the whole database table.
+    pub to_table: MapProof<Hash, Hash>,
+    /// Proof of the specific proposal in this table.
+    pub to_proposal: MapProof<Hash, TransferProposal>,
+}
+
+/// Transfer proposal information.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProposalInfo {
+    /// Proof of the last block.
+    pub block_proof: BlockProof,
+    /// Proof of the proposal in the table of pending proposals.
+    pub pending_proof: ProposalProof,
+    /// Proof of the proposal in the archive of finished proposals.
+    pub archive_proof: ProposalProof,
+}
+
+/// Describes the query parameters for the `get_pending_proposals` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PendingProposalsQuery {
+    /// Name of the sender's wallet.
+    pub wallet: String,
+}
+
+/// Proof of existence for the ids of the pending transfer proposals of a wallet.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WalletProposalsProof {
+    /// Proof of the whole database table.
+    pub to_table: MapProof<Hash, Hash>,
+    /// Proof of the hash of the proposal ids in this table.
+    pub to_ids: MapProof<Hash, Hash>,
+    /// Proof of the list of proposal ids, if the wallet has any pending proposals.
+    pub ids: Option<ListProof<Hash>>,
+}
+
+/// Transfer proposal awaiting approvals.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PendingProposal {
+    /// Id of the proposal.
+    pub id: Hash,
+    /// The proposal itself, including the amount, recipient and existing approvals.
+    pub proposal: TransferProposal,
+    /// Weight of the approvals still needed to reach the quorum of the sender's wallet.
+    pub remaining_quorum: u32,
+}
+
+/// Pending transfer proposals of a wallet.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WalletProposals {
+    /// Name of the sender's wallet.
+    pub wallet: String,
+    /// Proof of the ids of the pending proposals.
+    pub ids_proof: WalletProposalsProof,
+    /// Proof of the pending proposals in the table of transfer proposals.
+    pub proposal_proof: ProposalProof,
+    /// Pending proposals listed in the order of their creation.
+    pub proposals: Vec<PendingProposal>,
+}
+
+/// Pending transfer proposals of a wallet.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PendingProposalsInfo {
+    /// Proof of the last block.
+    pub block_proof: BlockProof,
+    /// Proofs of the pending proposals.
+    pub wallet_proposals: WalletProposals,
+}
+
+/// Transfer proposals awaiting the signature of a public key.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AwaitingProposalsInfo {
+    /// Proof of the last block.
+    pub block_proof: BlockProof,
+    /// Proof of the names of the wallets owned by the key.
+    pub key_wallets_proof: KeyWalletsProof,
+    /// Pending proposals of each wallet which the key has neither approved nor rejected.
+    pub wallets: Vec<WalletProposals>,
+}
+
+/// The structure describes the query parameters for the `get_htlc` endpoint.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct HtlcQuery {
+    /// Id of the queried hash time-locked contract.
+    pub id: Hash,
+}
+
+/// Proof of existence for specific hash time-locked contract.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HtlcProof {
+    /// Proof of the whole database table.
+    pub to_table: MapProof<Hash, Hash>,
+    /// Proof of the specific lock in this table.
+    pub to_htlc: MapProof<Hash, Htlc>,
+}
+
+/// Hash time-locked contract information.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HtlcInfo {
+    /// Proof of the last block.
+    pub block_proof: BlockProof,
+    /// Proof of the lock.
+    pub htlc_proof: HtlcProof,
+}
+
+/// The structure describes the query parameters for the `get_allowance` endpoint.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AllowanceQuery {
+    /// Name of the owner's wallet.
+    pub owner: String,
+    /// Name of the spender's wallet.
+    pub spender: String,
+}
+
+/// Proof of existence for specific allowance.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AllowanceProof {
+    /// Proof of the whole database table.
+    pub to_table: MapProof<Hash, Hash>,
+    /// Proof of the specific allowance in this table.
+    pub to_allowance: MapProof<Hash, Allowance>,
+}
+
+/// Allowance information.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AllowanceInfo {
+    /// Proof of the last block.
+    pub block_proof: BlockProof,
+    /// Proof of the allowance.
+    pub allowance_proof: AllowanceProof,
+}
+
+/// The structure describes the query parameters for the `get_asset` endpoint.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AssetQuery {
+    /// Name of the queried asset.
+    pub name: String,
+}
+
+/// Proof of existence for specific asset.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AssetProof {
+    /// Proof of the whole database table.
+    pub to_table: MapProof<Hash, Hash>,
+    /// Proof of the specific asset in this table.
+    pub to_asset: MapProof<Hash, Asset>,
+}
+
+/// Asset information.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AssetInfo {
+    /// Proof of the last block.
+    pub block_proof: BlockProof,
+    /// Proof of the asset.
+    pub asset_proof: AssetProof,
+}
+
+/// The structure describes the query parameters for the `get_balance` endpoint.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BalanceQuery {
+    /// Name of the wallet.
+    pub wallet: String,
+    /// Name of the asset.
+    pub asset: String,
+}
+
+/// Proof of existence for specific balance of an asset.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BalanceProof {
+    /// Proof of the whole database table.
+    pub to_table: MapProof<Hash, Hash>,
+    /// Proof of the specific balance in this table.
+    pub to_balance: MapProof<Hash, u64>,
+}
+
+/// Balance of a wallet in an asset. Balances in the native currency are part of the wallets.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BalanceInfo {
+    /// Proof of the last block.
+    pub block_proof: BlockProof,
+    /// Proof of the balance.
+    pub balance_proof: BalanceProof,
+}
+
+/// The structure describes the query parameters for the `get_fee` endpoint.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FeeQuery {
+    /// Hash of the transaction.
+    pub transaction: Hash,
+}
+
+/// Proof of existence for specific fee.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeeProof {
+    /// Proof of the whole database table.
+    pub to_table: MapProof<Hash, Hash>,
+    /// Proof of the specific fee in this table.
+    pub to_fee: MapProof<Hash, u64>,
+}
+
+/// Fee paid for a transaction.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeeInfo {
+    /// Proof of the last block.
+    pub block_proof: BlockProof,
+    /// Proof of the fee.
+    pub fee_proof: FeeProof,
+}
+
+/// The structure describes the query parameters for the `open_invoices` endpoint.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct InvoicesQuery {
+    /// Name of the payee's wallet.
+    pub payee: String,
+}
+
+/// Invoice which can still be paid.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OpenInvoice {
+    /// Id of the invoice.
+    pub id: Hash,
+    /// The invoice itself.
+    pub invoice: Invoice,
+}
+
+/// Total supply of the currency.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SupplyInfo {
+    /// Proof of the last block.
+    pub block_proof: BlockProof,
+    /// Proof of the hash of the total supply entry.
+    pub to_table: MapProof<Hash, Hash>,
+    /// Total amount of currency in all wallets.
+    pub total_supply: u64,
+}
+
+/// Returns the memo of the transfer made by the given transaction. Approvals of transfer
+/// proposals carry the memo of the approved proposal.
+fn transaction_memo<T: AsRef<dyn Snapshot>>(schema: &Schema<T>, transaction: &TransactionMessage) -> String {
+    let transaction = match WalletTransactions::tx_from_raw(transaction.raw_transaction()) {
+        Ok(transaction) => transaction,
+        Err(_) => return String::new(),
+    };
+    match transaction {
+        WalletTransactions::Transfer(transfer) => transfer.memo,
+        WalletTransactions::ProposeTransfer(propose) => propose.memo,
+        WalletTransactions::ApproveTransfer(approve) => schema
+            .transfer_proposal(&approve.proposal_id)
+            .or_else(|| schema.archived_transfer_proposal(&approve.proposal_id))
+            .map(|proposal| proposal.memo)
+            .unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+/// Returns the pending transfer proposals of the wallet with proofs, keeping only the
+/// proposals accepted by the filter.
+fn wallet_proposals<T, F>(
+    general_schema: &blockchain::Schema<T>,
+    currency_schema: &Schema<T>,
+    wallet: &str,
+    filter: F,
+) -> WalletProposals
+where
+    T: AsRef<dyn Snapshot>,
+    F: Fn(&TransferProposal) -> bool,
+{
+    let wallet_hash = hash(wallet.as_bytes());
+    let ids = currency_schema.wallet_proposal_ids(&wallet_hash);
+    let ids_proof = WalletProposalsProof {
+        to_table: general_schema
+            .get_proof_to_service_table(CRYPTOCURRENCY_SERVICE_ID, schema::WALLET_PROPOSALS_TABLE),
+        to_ids: currency_schema.wallet_proposals().get_proof(wallet_hash),
+        ids: if ids.is_empty() {
+            None
+        } else {
+            Some(ids.get_range_proof(0, ids.len()))
+        },
+    };
+
+    let proposal_proof = ProposalProof {
+        to_table: general_schema
+            .get_proof_to_service_table(CRYPTOCURRENCY_SERVICE_ID, schema::TRANSFER_PROPOSALS_TABLE),
+        to_proposal: currency_schema.transfer_proposals().get_multiproof(ids.iter()),
+    };
+
+    let sender = currency_schema.wallet(&wallet_hash);
+    let height = general_schema.height().next();
+    let proposals = ids
+        .iter()
+        .filter_map(|id| currency_schema.transfer_proposal(&id).map(|proposal| (id, proposal)))
+        .filter(|(_, proposal)| filter(proposal))
+        .map(|(id, proposal)| {
+            let remaining_quorum = sender.as_ref().map_or(0, |sender| {
+                required_quorum(currency_schema, &proposal, sender, height)
+                    .saturating_sub(sender.count_approvals(&proposal.approvals))
+            });
+            PendingProposal {
+                id,
+                proposal,
+                remaining_quorum,
+            }
+        })
+        .collect();
+
+    WalletProposals {
+        wallet: wallet.to_owned(),
+        ids_proof,
+        proposal_proof,
+        proposals,
+    }
+}
+
 /// Public service API description.
 #[derive(Debug, Clone, Copy)]
 pub struct PublicApi;
@@ -81,7 +453,8 @@ impl PublicApi {
             .unwrap();
 
         let to_table: MapProof<Hash, Hash> =
-            general_schema.get_proof_to_service_table(CRYPTOCURRENCY_SERVICE_ID, 0);
+            general_schema
+                .get_proof_to_service_table(CRYPTOCURRENCY_SERVICE_ID, schema::WALLETS_TABLE);
 
         let to_wallet: MapProof<Hash, Wallet> =
             currency_schema.wallets().get_proof(name_hash);
@@ -99,14 +472,40 @@ impl PublicApi {
             let history = currency_schema.wallet_history(&name_hash);
             let proof = history.get_range_proof(0, history.len());
 
-            let transactions = history
+            // Records which are not transactions are events of the service, such as expired
+            // transfer proposals, which are proven separately.
+            let (transactions, events): (Vec<_>, Vec<_>) = history
+                .iter()
+                .map(|record| (record, explorer.transaction_without_proof(&record)))
+                .partition(|(_, transaction)| transaction.is_some());
+            let transactions = transactions
+                .into_iter()
+                .filter_map(|(_, transaction)| transaction)
+                .collect::<Vec<_>>();
+            let events = WalletEventsProof {
+                to_table: general_schema
+                    .get_proof_to_service_table(CRYPTOCURRENCY_SERVICE_ID, schema::WALLET_EVENTS_TABLE),
+                to_events: currency_schema
+                    .wallet_events()
+                    .get_multiproof(events.into_iter().map(|(record, _)| record)),
+            };
+
+            let memos = transactions
+                .iter()
+                .map(|transaction| transaction_memo(&currency_schema, transaction))
+                .collect::<Vec<_>>();
+
+            let fees = transactions
                 .iter()
-                .map(|record| explorer.transaction_without_proof(&record).unwrap())
+                .map(|transaction| currency_schema.fee(&transaction.message().hash()))
                 .collect::<Vec<_>>();
 
             WalletHistory {
                 proof,
                 transactions,
+                memos,
+                fees,
+                events,
             }
         });
 
@@ -117,10 +516,327 @@ impl PublicApi {
         })
     }
 
-    /// Wires the above endpoint to public scope of the given `ServiceApiBuilder`.
+    /// Endpoint for getting the wallets owned by a public key.
+    pub fn wallets_by_key(state: &ServiceApiState, query: KeyQuery) -> api::Result<KeyWalletsInfo> {
+        let snapshot = state.snapshot();
+        let general_schema = blockchain::Schema::new(&snapshot);
+        let currency_schema = Schema::new(&snapshot);
+
+        let max_height = general_schema.block_hashes_by_height().len() - 1;
+
+        let block_proof = general_schema
+            .block_and_precommits(Height(max_height))
+            .unwrap();
+
+        let names = currency_schema.key_wallet_names(&query.pub_key);
+        let key_wallets_proof = KeyWalletsProof {
+            to_table: general_schema
+                .get_proof_to_service_table(CRYPTOCURRENCY_SERVICE_ID, schema::KEY_WALLETS_TABLE),
+            to_names: currency_schema.key_wallets().get_proof(query.pub_key),
+            names: if names.is_empty() {
+                None
+            } else {
+                Some(names.get_range_proof(0, names.len()))
+            },
+        };
+
+        let name_hashes = names.iter().map(|name| hash(name.as_bytes()));
+        let wallet_proof = WalletProof {
+            to_table: general_schema
+                .get_proof_to_service_table(CRYPTOCURRENCY_SERVICE_ID, schema::WALLETS_TABLE),
+            to_wallet: currency_schema.wallets().get_multiproof(name_hashes),
+        };
+
+        Ok(KeyWalletsInfo {
+            block_proof,
+            key_wallets_proof,
+            wallet_proof,
+        })
+    }
+
+    /// Endpoint for getting a single transfer proposal.
+    pub fn proposal_info(
+        state: &ServiceApiState,
+        query: ProposalQuery,
+    ) -> api::Result<ProposalInfo> {
+        let snapshot = state.snapshot();
+        let general_schema = blockchain::Schema::new(&snapshot);
+        let currency_schema = Schema::new(&snapshot);
+
+        let max_height = general_schema.block_hashes_by_height().len() - 1;
+
+        let block_proof = general_schema
+            .block_and_precommits(Height(max_height))
+            .unwrap();
+
+        let pending_proof = ProposalProof {
+            to_table: general_schema
+                .get_proof_to_service_table(CRYPTOCURRENCY_SERVICE_ID, schema::TRANSFER_PROPOSALS_TABLE),
+            to_proposal: currency_schema.transfer_proposals().get_proof(query.id),
+        };
+
+        let archive_proof = ProposalProof {
+            to_table: general_schema
+                .get_proof_to_service_table(CRYPTOCURRENCY_SERVICE_ID, schema::TRANSFER_PROPOSALS_ARCHIVE_TABLE),
+            to_proposal: currency_schema
+                .transfer_proposals_archive()
+                .get_proof(query.id),
+        };
+
+        Ok(ProposalInfo {
+            block_proof,
+            pending_proof,
+            archive_proof,
+        })
+    }
+
+    /// Endpoint for getting the pending transfer proposals of a wallet.
+    pub fn pending_proposals(
+        state: &ServiceApiState,
+        query: PendingProposalsQuery,
+    ) -> api::Result<PendingProposalsInfo> {
+        let snapshot = state.snapshot();
+        let general_schema = blockchain::Schema::new(&snapshot);
+        let currency_schema = Schema::new(&snapshot);
+
+        let max_height = general_schema.block_hashes_by_height().len() - 1;
+
+        let block_proof = general_schema
+            .block_and_precommits(Height(max_height))
+            .unwrap();
+
+        let wallet_proposals = wallet_proposals(&general_schema, &currency_schema, &query.wallet, |_| true);
+
+        Ok(PendingProposalsInfo {
+            block_proof,
+            wallet_proposals,
+        })
+    }
+
+    /// Endpoint for getting the transfer proposals awaiting the signature of a public key.
+    pub fn awaiting_proposals(
+        state: &ServiceApiState,
+        query: KeyQuery,
+    ) -> api::Result<AwaitingProposalsInfo> {
+        let snapshot = state.snapshot();
+        let general_schema = blockchain::Schema::new(&snapshot);
+        let currency_schema = Schema::new(&snapshot);
+
+        let max_height = general_schema.block_hashes_by_height().len() - 1;
+
+        let block_proof = general_schema
+            .block_and_precommits(Height(max_height))
+            .unwrap();
+
+        let names = currency_schema.key_wallet_names(&query.pub_key);
+        let key_wallets_proof = KeyWalletsProof {
+            to_table: general_schema
+                .get_proof_to_service_table(CRYPTOCURRENCY_SERVICE_ID, schema::KEY_WALLETS_TABLE),
+            to_names: currency_schema.key_wallets().get_proof(query.pub_key),
+            names: if names.is_empty() {
+                None
+            } else {
+                Some(names.get_range_proof(0, names.len()))
+            },
+        };
+
+        let awaiting = |proposal: &TransferProposal| {
+            !proposal.is_approved_by(&query.pub_key) && !proposal.is_rejected_by(&query.pub_key)
+        };
+        let wallets = names
+            .iter()
+            .map(|name| wallet_proposals(&general_schema, &currency_schema, &name, awaiting))
+            .collect();
+
+        Ok(AwaitingProposalsInfo {
+            block_proof,
+            key_wallets_proof,
+            wallets,
+        })
+    }
+
+    /// Endpoint for getting a single hash time-locked contract.
+    pub fn htlc_info(state: &ServiceApiState, query: HtlcQuery) -> api::Result<HtlcInfo> {
+        let snapshot = state.snapshot();
+        let general_schema = blockchain::Schema::new(&snapshot);
+        let currency_schema = Schema::new(&snapshot);
+
+        let max_height = general_schema.block_hashes_by_height().len() - 1;
+
+        let block_proof = general_schema
+            .block_and_precommits(Height(max_height))
+            .unwrap();
+
+        let htlc_proof = HtlcProof {
+            to_table: general_schema
+                .get_proof_to_service_table(CRYPTOCURRENCY_SERVICE_ID, schema::HTLCS_TABLE),
+            to_htlc: currency_schema.htlcs().get_proof(query.id),
+        };
+
+        Ok(HtlcInfo {
+            block_proof,
+            htlc_proof,
+        })
+    }
+
+    /// Endpoint for getting the allowance of the spender's wallet on the owner's wallet.
+    pub fn allowance_info(state: &ServiceApiState, query: AllowanceQuery) -> api::Result<AllowanceInfo> {
+        let snapshot = state.snapshot();
+        let general_schema = blockchain::Schema::new(&snapshot);
+        let currency_schema = Schema::new(&snapshot);
+
+        let max_height = general_schema.block_hashes_by_height().len() - 1;
+
+        let block_proof = general_schema
+            .block_and_precommits(Height(max_height))
+            .unwrap();
+
+        let allowance_proof = AllowanceProof {
+            to_table: general_schema
+                .get_proof_to_service_table(CRYPTOCURRENCY_SERVICE_ID, schema::ALLOWANCES_TABLE),
+            to_allowance: currency_schema
+                .allowances()
+                .get_proof(allowance_key(&query.owner, &query.spender)),
+        };
+
+        Ok(AllowanceInfo {
+            block_proof,
+            allowance_proof,
+        })
+    }
+
+    /// Endpoint for getting a single asset.
+    pub fn asset_info(state: &ServiceApiState, query: AssetQuery) -> api::Result<AssetInfo> {
+        let snapshot = state.snapshot();
+        let general_schema = blockchain::Schema::new(&snapshot);
+        let currency_schema = Schema::new(&snapshot);
+
+        let max_height = general_schema.block_hashes_by_height().len() - 1;
+
+        let block_proof = general_schema
+            .block_and_precommits(Height(max_height))
+            .unwrap();
+
+        let asset_proof = AssetProof {
+            to_table: general_schema
+                .get_proof_to_service_table(CRYPTOCURRENCY_SERVICE_ID, schema::ASSETS_TABLE),
+            to_asset: currency_schema.assets().get_proof(hash(query.name.as_bytes())),
+        };
+
+        Ok(AssetInfo {
+            block_proof,
+            asset_proof,
+        })
+    }
+
+    /// Endpoint for getting the balance of a wallet in an asset.
+    pub fn balance_info(state: &ServiceApiState, query: BalanceQuery) -> api::Result<BalanceInfo> {
+        let snapshot = state.snapshot();
+        let general_schema = blockchain::Schema::new(&snapshot);
+        let currency_schema = Schema::new(&snapshot);
+
+        let max_height = general_schema.block_hashes_by_height().len() - 1;
+
+        let block_proof = general_schema
+            .block_and_precommits(Height(max_height))
+            .unwrap();
+
+        let balance_proof = BalanceProof {
+            to_table: general_schema
+                .get_proof_to_service_table(CRYPTOCURRENCY_SERVICE_ID, schema::ASSET_BALANCES_TABLE),
+            to_balance: currency_schema
+                .asset_balances()
+                .get_proof(asset_balance_key(&query.wallet, &query.asset)),
+        };
+
+        Ok(BalanceInfo {
+            block_proof,
+            balance_proof,
+        })
+    }
+
+    /// Endpoint for getting the fee paid for a transaction.
+    pub fn fee_info(state: &ServiceApiState, query: FeeQuery) -> api::Result<FeeInfo> {
+        let snapshot = state.snapshot();
+        let general_schema = blockchain::Schema::new(&snapshot);
+        let currency_schema = Schema::new(&snapshot);
+
+        let max_height = general_schema.block_hashes_by_height().len() - 1;
+
+        let block_proof = general_schema
+            .block_and_precommits(Height(max_height))
+            .unwrap();
+
+        let fee_proof = FeeProof {
+            to_table: general_schema
+                .get_proof_to_service_table(CRYPTOCURRENCY_SERVICE_ID, schema::FEES_TABLE),
+            to_fee: currency_schema.fees().get_proof(query.transaction),
+        };
+
+        Ok(FeeInfo {
+            block_proof,
+            fee_proof,
+        })
+    }
+
+    /// Endpoint for getting the invoices of the payee's wallet which can still be paid.
+    pub fn open_invoices(state: &ServiceApiState, query: InvoicesQuery) -> api::Result<Vec<OpenInvoice>> {
+        let snapshot = state.snapshot();
+        let general_schema = blockchain::Schema::new(&snapshot);
+        let currency_schema = Schema::new(&snapshot);
+
+        // Invoices listed as open can be paid in the next block.
+        let height = general_schema.height().next();
+
+        let invoices = currency_schema
+            .payee_invoices(&hash(query.payee.as_bytes()))
+            .iter()
+            .filter_map(|id| currency_schema.invoice(&id).map(|invoice| OpenInvoice { id, invoice }))
+            .filter(|open| open.invoice.is_open_at(height))
+            .collect();
+        Ok(invoices)
+    }
+
+    /// Endpoint for getting the total supply of the currency.
+    pub fn supply_info(state: &ServiceApiState, _query: ()) -> api::Result<SupplyInfo> {
+        let snapshot = state.snapshot();
+        let general_schema = blockchain::Schema::new(&snapshot);
+        let currency_schema = Schema::new(&snapshot);
+
+        let max_height = general_schema.block_hashes_by_height().len() - 1;
+
+        let block_proof = general_schema
+            .block_and_precommits(Height(max_height))
+            .unwrap();
+
+        let to_table: MapProof<Hash, Hash> =
+            general_schema
+                .get_proof_to_service_table(CRYPTOCURRENCY_SERVICE_ID, schema::TOTAL_SUPPLY_TABLE);
+
+        let total_supply = currency_schema.total_supply().get().unwrap_or_default();
+
+        Ok(SupplyInfo {
+            block_proof,
+            to_table,
+            total_supply,
+        })
+    }
+
+    /// Wires the above endpoints to public scope of the given `ServiceApiBuilder`.
     pub fn wire(builder: &mut ServiceApiBuilder) {
         builder
             .public_scope()
-            .endpoint("v1/wallets/info", Self::wallet_info);
+            .endpoint("v1/wallets/info", Self::wallet_info)
+            .endpoint("v1/wallets/by-key", Self::wallets_by_key)
+            .endpoint("v1/proposals/info", Self::proposal_info)
+            .endpoint("v1/proposals/pending", Self::pending_proposals)
+            .endpoint("v1/proposals/awaiting", Self::awaiting_proposals)
+            .endpoint("v1/htlcs/info", Self::htlc_info)
+            .endpoint("v1/allowances/info", Self::allowance_info)
+            .endpoint("v1/invoices/open", Self::open_invoices)
+            .endpoint("v1/assets/info", Self::asset_info)
+            .endpoint("v1/assets/balance", Self::balance_info)
+            .endpoint("v1/fees/info", Self::fee_info)
+            .endpoint("v1/supply", Self::supply_info);
     }
 }
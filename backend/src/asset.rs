@@ -0,0 +1,46 @@
+//! Asset issued on the chain
+
+use exonum::crypto::{self, Hash, HashStream};
+
+use super::proto;
+
+/// Name of the native currency, which is kept in the balances of the wallets.
+pub const NATIVE_ASSET: &str = "";
+
+/// Asset issued on the chain in addition to the native currency.
+#[derive(Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::Asset", serde_pb_convert)]
+pub struct Asset {
+    /// Unique name of the asset.
+    pub name: String,
+    /// Name of the wallet whose owners may issue the asset.
+    pub issuer: String,
+    /// Total issued amount of the asset.
+    pub supply: u64,
+}
+
+impl Asset {
+    /// Create new asset.
+    pub fn new(name: &str, issuer: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            issuer: issuer.to_owned(),
+            supply: 0,
+        }
+    }
+    /// Returns a copy of this asset with the given amount added to its supply, or `None`
+    /// if the supply would overflow.
+    pub fn issue(self, amount: u64) -> Option<Self> {
+        let supply = self.supply.checked_add(amount)?;
+        Some(Self { supply, ..self })
+    }
+}
+
+/// Returns the key of the balance of the asset in the database, built from the hashes
+/// of the wallet and the asset names.
+pub fn asset_balance_key(wallet: &str, asset: &str) -> Hash {
+    HashStream::new()
+        .update(crypto::hash(wallet.as_bytes()).as_ref())
+        .update(crypto::hash(asset.as_bytes()).as_ref())
+        .hash()
+}
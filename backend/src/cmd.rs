@@ -0,0 +1,220 @@
+// Copyright 2019 The Exonum Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Command line extensions which put the service configuration into the node configs.
+
+use exonum::{
+    crypto::PublicKey,
+    helpers::fabric::{keys, Argument, CommandExtension, Context},
+    node::NodeConfig,
+};
+use hex::FromHex;
+use toml::Value;
+
+use std::fs;
+
+use crate::{
+    config::{GenesisConfig, GenesisWallet},
+    SERVICE_NAME,
+};
+
+/// Wallets read from the file passed with `--genesis-wallets`.
+#[derive(Debug, Deserialize)]
+struct GenesisWallets {
+    #[serde(default)]
+    wallets: Vec<GenesisWallet>,
+}
+
+/// Returns the arguments which configure the genesis block of the service.
+fn genesis_args() -> Vec<Argument> {
+    vec![
+        Argument::new_named(
+            "INITIAL_BALANCE",
+            false,
+            "Balance credited to the wallets created with `CreateWallet`",
+            None,
+            "initial-balance",
+            false,
+        ),
+        Argument::new_named(
+            "ISSUER_KEY",
+            false,
+            "Public key which is allowed to issue currency",
+            None,
+            "issuer-key",
+            true,
+        ),
+        Argument::new_named(
+            "GENESIS_WALLETS",
+            false,
+            "Path to the TOML file with the wallets created in the genesis block",
+            None,
+            "genesis-wallets",
+            false,
+        ),
+    ]
+}
+
+/// Returns the genesis configuration of the service from the command line arguments.
+fn genesis_config(context: &Context) -> Result<GenesisConfig, failure::Error> {
+    let mut genesis = GenesisConfig::default();
+
+    if let Ok(value) = context.arg::<String>("INITIAL_BALANCE") {
+        genesis.config.initial_balance = value
+            .parse()
+            .map_err(|e| format_err!("Invalid initial balance {}: {}", value, e))?;
+    }
+
+    for key in context.arg_multiple::<String>("ISSUER_KEY").unwrap_or_default() {
+        let key = PublicKey::from_hex(&key)
+            .map_err(|e| format_err!("Invalid issuer key {}: {}", key, e))?;
+        genesis.config.issuers.push(key);
+    }
+
+    if let Ok(path) = context.arg::<String>("GENESIS_WALLETS") {
+        let wallets: GenesisWallets = toml::from_str(&fs::read_to_string(&path)?)?;
+        genesis.wallets = wallets.wallets;
+    }
+
+    genesis.validate()?;
+    Ok(genesis)
+}
+
+/// Puts the service configuration into the common config template.
+#[derive(Debug)]
+pub struct GenerateCommonConfig;
+
+impl CommandExtension for GenerateCommonConfig {
+    fn args(&self) -> Vec<Argument> {
+        genesis_args()
+    }
+
+    fn execute(&self, mut context: Context) -> Result<Context, failure::Error> {
+        let mut values = context.get(keys::SERVICES_CONFIG)?;
+        values.insert(SERVICE_NAME.to_owned(), Value::try_from(genesis_config(&context)?)?);
+        context.set(keys::SERVICES_CONFIG, values);
+        Ok(context)
+    }
+}
+
+/// Copies the service configuration from the common config template to the node config.
+#[derive(Debug)]
+pub struct Finalize;
+
+impl CommandExtension for Finalize {
+    fn args(&self) -> Vec<Argument> {
+        vec![]
+    }
+
+    fn execute(&self, mut context: Context) -> Result<Context, failure::Error> {
+        let mut node_config: NodeConfig = context.get(keys::NODE_CONFIG)?;
+        let common_config = context.get(keys::COMMON_CONFIG)?;
+
+        if let Some(value) = common_config.services_config.get(SERVICE_NAME) {
+            node_config.services_configs.insert(SERVICE_NAME.to_owned(), value.clone());
+        }
+        context.set(keys::NODE_CONFIG, node_config);
+        Ok(context)
+    }
+}
+
+/// Puts the service configuration into the configs of the testnet nodes.
+#[derive(Debug)]
+pub struct GenerateTestnet;
+
+impl CommandExtension for GenerateTestnet {
+    fn args(&self) -> Vec<Argument> {
+        genesis_args()
+    }
+
+    fn execute(&self, mut context: Context) -> Result<Context, failure::Error> {
+        let value = Value::try_from(genesis_config(&context)?)?;
+
+        let mut configs = context.get(keys::CONFIGS)?;
+        for config in &mut configs {
+            config.services_configs.insert(SERVICE_NAME.to_owned(), value.clone());
+        }
+        context.set(keys::CONFIGS, configs);
+        Ok(context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use exonum::crypto::gen_keypair;
+
+    use super::*;
+    use crate::config::ServiceConfig;
+
+    #[test]
+    fn test_genesis_config_defaults() {
+        let genesis = genesis_config(&Context::default()).unwrap();
+        assert_eq!(genesis, GenesisConfig::default());
+    }
+
+    #[test]
+    fn test_genesis_config_from_args() {
+        let (issuer, _) = gen_keypair();
+        let mut context = Context::default();
+        context.set_arg("INITIAL_BALANCE", "50".to_owned());
+        context.set_arg_multiple("ISSUER_KEY", vec![issuer.to_hex()]);
+
+        let genesis = genesis_config(&context).unwrap();
+        assert_eq!(genesis.config.initial_balance, 50);
+        assert_eq!(genesis.config.issuers, vec![issuer]);
+        assert!(genesis.wallets.is_empty());
+    }
+
+    #[test]
+    fn test_genesis_config_invalid_initial_balance() {
+        let mut context = Context::default();
+        context.set_arg("INITIAL_BALANCE", "ten".to_owned());
+
+        let err = genesis_config(&context).unwrap_err();
+        assert!(err.to_string().starts_with("Invalid initial balance ten"));
+    }
+
+    #[test]
+    fn test_genesis_config_invalid_issuer_key() {
+        let mut context = Context::default();
+        context.set_arg_multiple("ISSUER_KEY", vec!["not a key".to_owned()]);
+
+        assert!(genesis_config(&context).is_err());
+    }
+
+    #[test]
+    fn test_genesis_config_toml_roundtrip() {
+        let (issuer, _) = gen_keypair();
+        let (owner, _) = gen_keypair();
+        let genesis = GenesisConfig {
+            config: ServiceConfig {
+                initial_balance: 50,
+                issuers: vec![issuer],
+                ..ServiceConfig::default()
+            },
+            wallets: vec![GenesisWallet {
+                name: "Alice".to_owned(),
+                pub_keys: vec![owner],
+                weights: vec![],
+                quorum: 1,
+                balance: 100,
+            }],
+        };
+
+        let value = Value::try_from(genesis.clone()).unwrap();
+        // The service config fields are flattened into the top level table.
+        assert_eq!(value.get("initial_balance"), Some(&Value::Integer(50)));
+        assert_eq!(value.try_into::<GenesisConfig>().unwrap(), genesis);
+    }
+}
@@ -0,0 +1,216 @@
+// Copyright 2019 The Exonum Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cryptocurrency service configuration.
+
+use exonum::{blockchain, crypto::PublicKey, helpers::Height, storage::Snapshot};
+
+use std::collections::BTreeMap;
+
+use crate::{INITIAL_BALANCE, MAX_NAME_LENGTH, PROPOSAL_LIFETIME, SERVICE_NAME};
+
+/// Names of the transactions which are charged with fixed fees.
+pub const FIXED_FEE_TRANSACTIONS: &[&str] = &[
+    "Transfer",
+    "ProposeTransfer",
+    "ApproveTransfer",
+    "RejectTransfer",
+    "BatchTransfer",
+    "CreateWallet",
+];
+
+/// Global configuration of the cryptocurrency service.
+///
+/// The configuration is stored in the `services` section of the blockchain
+/// configuration under the name of the service, so it can be changed by the
+/// validators through the configuration service. Transactions use the
+/// configuration which is actual at the height of the block being created.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServiceConfig {
+    /// Keys which are allowed to issue currency.
+    pub issuers: Vec<PublicKey>,
+    /// Maximum amount of currency which can exist, unlimited if not set.
+    pub max_supply: Option<u64>,
+    /// Name of the wallet which receives the fees, no fees are charged if not set.
+    /// Transactions which are charged with fees fail while the wallet doesn't exist.
+    pub treasury: Option<String>,
+    /// Fees charged for the transactions.
+    pub fees: FeeSchedule,
+    /// Balance credited to the wallets created with `CreateWallet`.
+    pub initial_balance: u64,
+    /// Maximum number of blocks during which a transfer proposal or a wallet operation
+    /// can be approved.
+    pub proposal_lifetime: u64,
+    /// Maximum length of the names of wallets and assets in bytes.
+    pub max_name_length: usize,
+}
+
+impl Default for ServiceConfig {
+    fn default() -> Self {
+        ServiceConfig {
+            issuers: Vec::new(),
+            max_supply: None,
+            treasury: None,
+            fees: FeeSchedule::default(),
+            initial_balance: INITIAL_BALANCE,
+            proposal_lifetime: PROPOSAL_LIFETIME,
+            max_name_length: MAX_NAME_LENGTH,
+        }
+    }
+}
+
+/// Configuration of the service in the node configuration, which is used to
+/// create the genesis block.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GenesisConfig {
+    /// Wallets created in the genesis block.
+    #[serde(default)]
+    pub wallets: Vec<GenesisWallet>,
+    /// Initial configuration of the service.
+    #[serde(flatten)]
+    pub config: ServiceConfig,
+}
+
+impl GenesisConfig {
+    /// Checks the service configuration, that the names of the genesis wallets fit into
+    /// the maximum length and that their total balance doesn't exceed the maximum supply.
+    pub fn validate(&self) -> Result<(), failure::Error> {
+        self.config.validate()?;
+        let mut supply = 0u64;
+        for wallet in &self.wallets {
+            if wallet.name.len() > self.config.max_name_length {
+                bail!("Name of genesis wallet {} is too long", wallet.name);
+            }
+            supply = match supply.checked_add(wallet.balance) {
+                Some(supply) => supply,
+                None => bail!("Total balance of genesis wallets overflows"),
+            };
+        }
+        if self.config.max_supply.map_or(false, |max_supply| supply > max_supply) {
+            bail!("Total balance of genesis wallets exceeds the maximum supply");
+        }
+        Ok(())
+    }
+}
+
+/// Wallet created in the genesis block.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GenesisWallet {
+    /// Name of the wallet.
+    pub name: String,
+    /// Public keys of the wallet owners.
+    pub pub_keys: Vec<PublicKey>,
+    /// Weights of the owners, all owners have the weight of 1 if empty.
+    #[serde(default)]
+    pub weights: Vec<u32>,
+    /// Total weight of approvals required for wallet operations.
+    pub quorum: u32,
+    /// Initial balance of the wallet.
+    #[serde(default)]
+    pub balance: u64,
+}
+
+/// Fees charged for the transactions, paid in the native currency.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeeSchedule {
+    /// Fixed fees keyed by the name of the transaction, e.g. `Transfer`.
+    ///
+    /// Fixed fees are charged for the transactions listed in `FIXED_FEE_TRANSACTIONS`.
+    /// The fee for `CreateWallet` is paid out of the initial balance of the new wallet.
+    #[serde(default)]
+    pub fixed: BTreeMap<String, u64>,
+    /// Fee for the transferred amount in basis points, charged when a transfer
+    /// of the native currency is executed.
+    #[serde(default)]
+    pub transfer_rate: u64,
+}
+
+impl FeeSchedule {
+    /// Returns fixed fee for the transaction with the given name.
+    pub fn fixed_fee(&self, transaction: &str) -> u64 {
+        self.fixed.get(transaction).cloned().unwrap_or_default()
+    }
+
+    /// Returns fee for transferring the given amount.
+    pub fn transfer_fee(&self, amount: u64) -> u64 {
+        (u128::from(amount) * u128::from(self.transfer_rate) / 10_000) as u64
+    }
+}
+
+impl ServiceConfig {
+    /// Returns the configuration which is actual for the next block.
+    ///
+    /// If the actual configuration of the service can't be parsed or is not valid, the
+    /// latest valid configuration before it is used instead, or the default one if there
+    /// is none.
+    pub fn actual(snapshot: &dyn Snapshot) -> Self {
+        let schema = blockchain::Schema::new(snapshot);
+        let next_height = Height(schema.block_hashes_by_height().len());
+        let references = schema.configs_actual_from();
+        let configs = (0..references.len())
+            .rev()
+            .filter_map(|index| references.get(index))
+            .filter(|reference| reference.actual_from() <= next_height)
+            .filter_map(|reference| schema.configs().get(reference.cfg_hash()));
+
+        for config in configs {
+            let value = match config.services.get(SERVICE_NAME) {
+                Some(value) => value,
+                None => return ServiceConfig::default(),
+            };
+            match ServiceConfig::from_value(value) {
+                Ok(config) => return config,
+                Err(e) => warn!("Skipping invalid cryptocurrency service configuration: {}", e),
+            }
+        }
+        warn!("No valid cryptocurrency service configuration, using the default one");
+        ServiceConfig::default()
+    }
+
+    /// Parses the configuration from its JSON representation and validates it.
+    fn from_value(value: &serde_json::Value) -> Result<Self, failure::Error> {
+        let config: ServiceConfig = serde_json::from_value(value.clone())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Returns the last height at which a transfer proposal or a wallet operation made
+    /// at the given height can be approved.
+    pub fn proposal_deadline(&self, height: Height) -> Height {
+        Height(height.0.saturating_add(self.proposal_lifetime))
+    }
+
+    /// Checks that the fixed fees are set for the supported transactions only and that
+    /// the fee for `CreateWallet` can be paid out of the initial balance.
+    pub fn validate(&self) -> Result<(), failure::Error> {
+        let unsupported = self
+            .fees
+            .fixed
+            .keys()
+            .find(|name| !FIXED_FEE_TRANSACTIONS.contains(&name.as_str()));
+        if let Some(name) = unsupported {
+            bail!("Fixed fee is set for unsupported transaction {}", name);
+        }
+        if self.fees.fixed_fee("CreateWallet") > self.initial_balance {
+            bail!("Fee for CreateWallet exceeds the initial balance");
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the given key is allowed to issue currency.
+    pub fn is_issuer(&self, key: &PublicKey) -> bool {
+        self.issuers.contains(key)
+    }
+}
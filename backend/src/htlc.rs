@@ -0,0 +1,98 @@
+//! Hash time-locked contract
+
+use exonum::{
+    crypto::{self, Hash},
+    helpers::Height,
+    proto::ProtobufConvert,
+};
+
+use super::proto;
+
+/// Lifecycle status of a hash time-locked contract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HtlcStatus {
+    /// Funds are locked until the preimage is revealed or the lock times out.
+    Locked,
+    /// Funds have been claimed by the receiver.
+    Claimed,
+    /// Funds have been refunded to the sender.
+    Refunded,
+}
+
+impl ProtobufConvert for HtlcStatus {
+    type ProtoStruct = proto::HtlcStatus;
+
+    fn to_pb(&self) -> Self::ProtoStruct {
+        match self {
+            HtlcStatus::Locked => proto::HtlcStatus::LOCKED,
+            HtlcStatus::Claimed => proto::HtlcStatus::CLAIMED,
+            HtlcStatus::Refunded => proto::HtlcStatus::REFUNDED,
+        }
+    }
+
+    fn from_pb(pb: Self::ProtoStruct) -> Result<Self, failure::Error> {
+        Ok(match pb {
+            proto::HtlcStatus::LOCKED => HtlcStatus::Locked,
+            proto::HtlcStatus::CLAIMED => HtlcStatus::Claimed,
+            proto::HtlcStatus::REFUNDED => HtlcStatus::Refunded,
+        })
+    }
+}
+
+/// Funds locked until the preimage of the hashlock is revealed or the lock times out.
+#[derive(Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::Htlc", serde_pb_convert)]
+pub struct Htlc {
+    /// Name of sender's wallet.
+    pub from: String,
+    /// Name of receiver's wallet.
+    pub to: String,
+    /// Amount of locked currency.
+    pub amount: u64,
+    /// SHA-256 hash of the preimage which unlocks the funds.
+    pub hashlock: Hash,
+    /// Last height at which the funds can be claimed.
+    pub timeout_height: Height,
+    /// Current status of the lock.
+    pub status: HtlcStatus,
+    /// Preimage revealed by the claim, empty until the funds are claimed.
+    pub preimage: Vec<u8>,
+}
+
+impl Htlc {
+    /// Create new lock.
+    pub fn new(from: &str, to: &str, amount: u64, hashlock: &Hash, timeout_height: Height) -> Self {
+        Self {
+            from: from.to_owned(),
+            to: to.to_owned(),
+            amount,
+            hashlock: *hashlock,
+            timeout_height,
+            status: HtlcStatus::Locked,
+            preimage: Vec::new(),
+        }
+    }
+    /// Returns `true` if the funds can no longer be claimed at the given height.
+    pub fn is_timed_out_at(&self, height: Height) -> bool {
+        height > self.timeout_height
+    }
+    /// Returns `true` if the given preimage unlocks the funds.
+    pub fn is_unlocked_by(&self, preimage: &[u8]) -> bool {
+        crypto::hash(preimage) == self.hashlock
+    }
+    /// Returns a copy of this lock claimed with the given preimage.
+    pub fn claim(self, preimage: &[u8]) -> Self {
+        Self {
+            status: HtlcStatus::Claimed,
+            preimage: preimage.to_vec(),
+            ..self
+        }
+    }
+    /// Returns a copy of this lock refunded to the sender.
+    pub fn refund(self) -> Self {
+        Self {
+            status: HtlcStatus::Refunded,
+            ..self
+        }
+    }
+}
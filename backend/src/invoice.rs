@@ -0,0 +1,76 @@
+//! Invoice
+
+use exonum::{helpers::Height, proto::ProtobufConvert};
+
+use super::proto;
+
+/// Lifecycle status of an invoice.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InvoiceStatus {
+    /// Invoice is waiting for the payment.
+    Open,
+    /// Invoice has been paid.
+    Paid,
+}
+
+impl ProtobufConvert for InvoiceStatus {
+    type ProtoStruct = proto::InvoiceStatus;
+
+    fn to_pb(&self) -> Self::ProtoStruct {
+        match self {
+            InvoiceStatus::Open => proto::InvoiceStatus::OPEN,
+            InvoiceStatus::Paid => proto::InvoiceStatus::PAID,
+        }
+    }
+
+    fn from_pb(pb: Self::ProtoStruct) -> Result<Self, failure::Error> {
+        Ok(match pb {
+            proto::InvoiceStatus::OPEN => InvoiceStatus::Open,
+            proto::InvoiceStatus::PAID => InvoiceStatus::Paid,
+        })
+    }
+}
+
+/// Request of the payee for a payment.
+#[derive(Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::Invoice", serde_pb_convert)]
+pub struct Invoice {
+    /// Name of payee's wallet.
+    pub payee: String,
+    /// Requested amount of currency.
+    pub amount: u64,
+    /// Reference of the payment, such as an invoice number.
+    pub memo: String,
+    /// Last height at which the invoice can be paid, `Height(0)` if the invoice doesn't expire.
+    pub expires_at: Height,
+    /// Current status of the invoice.
+    pub status: InvoiceStatus,
+}
+
+impl Invoice {
+    /// Create new invoice.
+    pub fn new(payee: &str, amount: u64, memo: &str, expires_at: Height) -> Self {
+        Self {
+            payee: payee.to_owned(),
+            amount,
+            memo: memo.to_owned(),
+            expires_at,
+            status: InvoiceStatus::Open,
+        }
+    }
+    /// Returns `true` if the invoice can no longer be paid at the given height.
+    pub fn is_expired_at(&self, height: Height) -> bool {
+        self.expires_at != Height(0) && height > self.expires_at
+    }
+    /// Returns `true` if the invoice can be paid at the given height.
+    pub fn is_open_at(&self, height: Height) -> bool {
+        self.status == InvoiceStatus::Open && !self.is_expired_at(height)
+    }
+    /// Returns a copy of this invoice marked as paid.
+    pub fn pay(self) -> Self {
+        Self {
+            status: InvoiceStatus::Paid,
+            ..self
+        }
+    }
+}
@@ -26,38 +26,82 @@ extern crate exonum_derive;
 #[macro_use]
 extern crate failure;
 #[macro_use]
+extern crate log;
+#[macro_use]
 extern crate serde_derive;
 
-pub use crate::schema::Schema;
+pub use crate::{
+    config::{GenesisConfig, ServiceConfig},
+    schema::Schema,
+};
 
+pub mod allowance;
 pub mod api;
+pub mod asset;
+mod cmd;
+pub mod config;
+pub mod htlc;
+pub mod invoice;
+pub mod pendingoperation;
 pub mod proto;
 pub mod schema;
+pub mod spendinglimit;
+pub mod subscription;
 pub mod transactions;
 pub mod wallet;
+pub mod walletevent;
 pub mod transferproposal;
 
 use exonum::{
     api::ServiceApiBuilder,
     blockchain::{self, Transaction, TransactionSet},
-    crypto::Hash,
-    helpers::fabric::{self, Context},
+    crypto::{self, Hash},
+    helpers::{
+        fabric::{self, keys, Command, CommandExtension, CommandName, Context},
+        Height,
+    },
     messages::RawTransaction,
-    storage::Snapshot,
+    storage::{Fork, Snapshot},
 };
 
-use crate::transactions::WalletTransactions;
+use crate::{
+    transactions::WalletTransactions,
+    walletevent::{WalletEvent, WalletEventKind},
+};
 
 /// Unique service ID.
 const CRYPTOCURRENCY_SERVICE_ID: u16 = 128;
 /// Name of the service.
 const SERVICE_NAME: &str = "cryptocurrency";
-/// Initial balance of the wallet.
+/// Default initial balance of the wallet.
 const INITIAL_BALANCE: u64 = 100;
+/// Default number of blocks during which a transfer proposal can be approved.
+const PROPOSAL_LIFETIME: u64 = 1000;
+/// Default maximum length of the names of wallets and assets in bytes.
+const MAX_NAME_LENGTH: usize = 64;
+/// Maximum length of the transfer memo in bytes.
+const MAX_MEMO_LENGTH: usize = 256;
 
 /// Exonum `Service` implementation.
 #[derive(Default, Debug)]
-pub struct Service;
+pub struct Service {
+    genesis: GenesisConfig,
+}
+
+impl Service {
+    /// Creates a service with the given initial configuration.
+    pub fn new(config: ServiceConfig) -> Self {
+        Service::with_genesis(GenesisConfig {
+            config,
+            ..GenesisConfig::default()
+        })
+    }
+
+    /// Creates a service with the given initial configuration and wallets.
+    pub fn with_genesis(genesis: GenesisConfig) -> Self {
+        Service { genesis }
+    }
+}
 
 impl blockchain::Service for Service {
     fn service_id(&self) -> u16 {
@@ -77,6 +121,48 @@ impl blockchain::Service for Service {
         WalletTransactions::tx_from_raw(raw).map(Into::into)
     }
 
+    fn initialize(&self, fork: &mut Fork) -> serde_json::Value {
+        self.genesis
+            .validate()
+            .unwrap_or_else(|e| panic!("Invalid service configuration: {}", e));
+        let mut schema = Schema::new(fork);
+        for wallet in &self.genesis.wallets {
+            transactions::check_owners(&wallet.pub_keys, &wallet.weights, wallet.quorum, &[])
+                .unwrap_or_else(|e| panic!("Invalid genesis wallet {}: {}", wallet.name, e));
+            if schema.wallet(&crypto::hash(wallet.name.as_bytes())).is_some() {
+                panic!("Duplicate genesis wallet {}", wallet.name);
+            }
+            // Genesis wallets are not created by a transaction, so their history starts
+            // with an event.
+            let event = WalletEvent::new(
+                WalletEventKind::GenesisWalletCreated,
+                &crypto::hash(wallet.name.as_bytes()),
+                Height(0),
+                wallet.balance,
+            );
+            let event_hash = schema.put_wallet_event(event);
+            schema.create_wallet(
+                &wallet.name,
+                &wallet.pub_keys,
+                &wallet.weights,
+                wallet.quorum,
+                &[],
+                wallet.balance,
+                &event_hash,
+            );
+        }
+        serde_json::to_value(&self.genesis.config).unwrap()
+    }
+
+    fn before_commit(&self, fork: &mut Fork) {
+        let height = blockchain::Schema::new(&*fork).height().next();
+        let mut schema = Schema::new(fork);
+        schema.execute_scheduled_transfers(height);
+        schema.execute_subscriptions(height);
+        schema.expire_transfer_proposals(height);
+        schema.expire_pending_operations(height);
+    }
+
     fn wire_api(&self, builder: &mut ServiceApiBuilder) {
         api::PublicApi::wire(builder);
     }
@@ -91,7 +177,22 @@ impl fabric::ServiceFactory for ServiceFactory {
         SERVICE_NAME
     }
 
-    fn make_service(&mut self, _: &Context) -> Box<dyn blockchain::Service> {
-        Box::new(Service)
+    fn command(&mut self, command: CommandName) -> Option<Box<dyn CommandExtension>> {
+        Some(match command {
+            v if v == fabric::GenerateCommonConfig.name() => Box::new(cmd::GenerateCommonConfig),
+            v if v == fabric::Finalize.name() => Box::new(cmd::Finalize),
+            v if v == fabric::GenerateTestnet.name() => Box::new(cmd::GenerateTestnet),
+            _ => return None,
+        })
+    }
+
+    fn make_service(&mut self, context: &Context) -> Box<dyn blockchain::Service> {
+        let genesis = context
+            .get(keys::NODE_CONFIG)
+            .ok()
+            .and_then(|config| config.services_configs.get(SERVICE_NAME).cloned())
+            .map(|value| value.try_into().expect("Invalid cryptocurrency service configuration"))
+            .unwrap_or_default();
+        Box::new(Service::with_genesis(genesis))
     }
 }
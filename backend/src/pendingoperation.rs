@@ -0,0 +1,42 @@
+//! Pending wallet operation
+
+use exonum::{crypto::PublicKey, helpers::Height};
+
+use super::proto;
+
+/// Approvals collected for a wallet operation which requires the quorum.
+#[derive(Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::PendingOperation", serde_pb_convert)]
+pub struct PendingOperation {
+    /// Name of the wallet.
+    pub wallet: String,
+    /// Keys which have already approved the operation.
+    pub approvals: Vec<PublicKey>,
+    /// Height after which the operation can no longer be approved.
+    pub valid_until: Height,
+}
+
+impl PendingOperation {
+    /// Create new pending operation.
+    pub fn new(wallet: &str, approvals: Vec<PublicKey>, valid_until: Height) -> Self {
+        Self {
+            wallet: wallet.to_owned(),
+            approvals,
+            valid_until,
+        }
+    }
+    /// Returns `true` if the operation can no longer be approved at the given height.
+    pub fn is_expired_at(&self, height: Height) -> bool {
+        height > self.valid_until
+    }
+    /// Returns `true` if the given key has already approved the operation.
+    pub fn is_approved_by(&self, key: &PublicKey) -> bool {
+        self.approvals.contains(key)
+    }
+    /// Returns a copy of this operation with the approval of the given key added.
+    pub fn add_approval(self, key: &PublicKey) -> Self {
+        let mut approvals = self.approvals;
+        approvals.push(*key);
+        Self { approvals, ..self }
+    }
+}
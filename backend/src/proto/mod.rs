@@ -17,7 +17,15 @@
 #![allow(bare_trait_objects)]
 #![allow(renamed_and_removed_lints)]
 
-pub use self::cryptocurrency::{CreateWallet, Issue, Transfer, Wallet, TransferProposal};
+pub use self::cryptocurrency::{
+    AddOwner, Allowance, Approve, ApproveTransfer, Asset, BatchTransfer, Burn, CancelSubscription,
+    CancelTransfer, ChangeQuorum, ChangeQuorumTiers, CreateAsset, CreateInvoice,
+    CreateSubscription, CreateWallet, Htlc, HtlcClaim, HtlcLock, HtlcRefund, HtlcStatus, Invoice,
+    InvoiceStatus, Issue, PayInvoice, PendingOperation, ProposalStatus, ProposeTransfer,
+    QuorumTier, RejectTransfer, RemoveOwner, ReplaceOwner, SetSpendingLimit, Spending,
+    SpendingLimit, Subscription, Transfer, TransferFrom, TransferOutput, TransferProposal, Wallet,
+    WalletEvent, WalletEventKind,
+};
 
 include!(concat!(env!("OUT_DIR"), "/protobuf_mod.rs"));
 
@@ -16,11 +16,23 @@
 
 use exonum::{
     crypto,
-    crypto::{Hash, PublicKey},
-    storage::{Fork, ProofListIndex, ProofMapIndex, Snapshot},
+    crypto::{CryptoHash, Hash, PublicKey},
+    helpers::Height,
+    storage::{Entry, Fork, ListIndex, ProofListIndex, ProofMapIndex, Snapshot},
 };
 
-use crate::{wallet::Wallet, transferproposal::TransferProposal, INITIAL_BALANCE};
+use crate::{
+    allowance::{allowance_key, Allowance},
+    asset::{asset_balance_key, Asset, NATIVE_ASSET},
+    htlc::Htlc,
+    invoice::Invoice,
+    pendingoperation::PendingOperation,
+    spendinglimit::SpendingLimit,
+    subscription::Subscription,
+    transferproposal::{ProposalStatus, TransferProposal},
+    wallet::{QuorumTier, Wallet},
+    walletevent::{WalletEvent, WalletEventKind},
+};
 
 /// Database schema for the cryptocurrency.
 #[derive(Debug)]
@@ -53,27 +65,258 @@ where
         ProofListIndex::new_in_family("cryptocurrency.wallet_history", hash, &self.view)
     }
 
+    /// Returns `ProofMapIndex` with the hashes of the lists of wallet names keyed by
+    /// the public key of their owner.
+    pub fn key_wallets(&self) -> ProofMapIndex<&T, PublicKey, Hash> {
+        ProofMapIndex::new("cryptocurrency.key_wallets", &self.view)
+    }
+
+    /// Returns names of the wallets owned by the given public key.
+    pub fn key_wallet_names(&self, key: &PublicKey) -> ProofListIndex<&T, String> {
+        ProofListIndex::new_in_family("cryptocurrency.key_wallet_names", key, &self.view)
+    }
+
     /// Returns wallet for the username hash.
     pub fn wallet(&self, hash: &Hash) -> Option<Wallet> {
         self.wallets().get(hash)
     }
 
-    /// Returns `ProofMapIndex` with wallets.
+    /// Returns `ProofMapIndex` with transfer proposals.
     pub fn transfer_proposals(&self) -> ProofMapIndex<&T, Hash, TransferProposal> {
         ProofMapIndex::new("cryptocurrency.transfer_proposals", &self.view)
     }
 
-    /// Returns proposals for the given tranfer hash.
+    /// Returns transfer proposal for the given id.
     pub fn transfer_proposal(&self, hash: &Hash) -> Option<TransferProposal> {
         self.transfer_proposals().get(hash)
     }
 
+    /// Returns `ProofMapIndex` with the hashes of the lists of pending transfer proposals
+    /// keyed by the hash of the sender's wallet name.
+    pub fn wallet_proposals(&self) -> ProofMapIndex<&T, Hash, Hash> {
+        ProofMapIndex::new("cryptocurrency.wallet_proposals", &self.view)
+    }
+
+    /// Returns ids of the pending transfer proposals from the wallet with the given name hash.
+    pub fn wallet_proposal_ids(&self, hash: &Hash) -> ProofListIndex<&T, Hash> {
+        ProofListIndex::new_in_family("cryptocurrency.wallet_proposal_ids", hash, &self.view)
+    }
+
+    /// Returns `ProofMapIndex` with finished transfer proposals.
+    pub fn transfer_proposals_archive(&self) -> ProofMapIndex<&T, Hash, TransferProposal> {
+        ProofMapIndex::new("cryptocurrency.transfer_proposals_archive", &self.view)
+    }
+
+    /// Returns finished transfer proposal for the given id.
+    pub fn archived_transfer_proposal(&self, hash: &Hash) -> Option<TransferProposal> {
+        self.transfer_proposals_archive().get(hash)
+    }
+
+    /// Returns ids of the transfer proposals which can be approved until the given height.
+    pub fn expiring_proposals(&self, height: Height) -> ListIndex<&T, Hash> {
+        ListIndex::new_in_family("cryptocurrency.expiring_proposals", &height.0, &self.view)
+    }
+
+    /// Returns ids of the transfer proposals scheduled for execution at the given height.
+    pub fn scheduled_proposals(&self, height: Height) -> ListIndex<&T, Hash> {
+        ListIndex::new_in_family("cryptocurrency.scheduled_proposals", &height.0, &self.view)
+    }
+
+    /// Returns ids of the pending operations of the wallet with the given name hash.
+    pub fn wallet_operations(&self, wallet: &Hash) -> ListIndex<&T, Hash> {
+        ListIndex::new_in_family("cryptocurrency.wallet_operations", wallet, &self.view)
+    }
+
+    /// Returns ids of the pending operations which can be approved until the given height.
+    pub fn expiring_operations(&self, height: Height) -> ListIndex<&T, Hash> {
+        ListIndex::new_in_family("cryptocurrency.expiring_operations", &height.0, &self.view)
+    }
+
+    /// Returns ids of the transfer proposals made to pay the invoice with the given id.
+    pub fn invoice_proposals(&self, invoice_id: &Hash) -> ListIndex<&T, Hash> {
+        ListIndex::new_in_family("cryptocurrency.invoice_proposals", invoice_id, &self.view)
+    }
+
+    /// Returns `Entry` with the total amount of currency in all wallets.
+    pub fn total_supply(&self) -> Entry<&T, u64> {
+        Entry::new("cryptocurrency.total_supply", &self.view)
+    }
+
+    /// Returns `ProofMapIndex` with wallet operations awaiting the quorum.
+    pub fn pending_operations(&self) -> ProofMapIndex<&T, Hash, PendingOperation> {
+        ProofMapIndex::new("cryptocurrency.pending_operations", &self.view)
+    }
+
+    /// Returns pending wallet operation for the given id.
+    pub fn pending_operation(&self, hash: &Hash) -> Option<PendingOperation> {
+        self.pending_operations().get(hash)
+    }
+
+    /// Returns `ProofMapIndex` with spending limits of the wallets.
+    pub fn spending_limits(&self) -> ProofMapIndex<&T, Hash, SpendingLimit> {
+        ProofMapIndex::new("cryptocurrency.spending_limits", &self.view)
+    }
+
+    /// Returns spending limit of the wallet with the given user name hash.
+    pub fn spending_limit(&self, hash: &Hash) -> Option<SpendingLimit> {
+        self.spending_limits().get(hash)
+    }
+
+    /// Returns `ProofMapIndex` with hash time-locked contracts.
+    pub fn htlcs(&self) -> ProofMapIndex<&T, Hash, Htlc> {
+        ProofMapIndex::new("cryptocurrency.htlcs", &self.view)
+    }
+
+    /// Returns hash time-locked contract for the given id.
+    pub fn htlc(&self, hash: &Hash) -> Option<Htlc> {
+        self.htlcs().get(hash)
+    }
+
+    /// Returns `ProofMapIndex` with allowances keyed by the owner's and spender's wallets.
+    pub fn allowances(&self) -> ProofMapIndex<&T, Hash, Allowance> {
+        ProofMapIndex::new("cryptocurrency.allowances", &self.view)
+    }
+
+    /// Returns allowance of the spender's wallet on the owner's wallet.
+    pub fn allowance(&self, owner: &str, spender: &str) -> Option<Allowance> {
+        self.allowances().get(&allowance_key(owner, spender))
+    }
+
+    /// Returns `ProofMapIndex` with subscriptions.
+    pub fn subscriptions(&self) -> ProofMapIndex<&T, Hash, Subscription> {
+        ProofMapIndex::new("cryptocurrency.subscriptions", &self.view)
+    }
+
+    /// Returns subscription for the given id.
+    pub fn subscription(&self, hash: &Hash) -> Option<Subscription> {
+        self.subscriptions().get(hash)
+    }
+
+    /// Returns `ProofMapIndex` with invoices.
+    pub fn invoices(&self) -> ProofMapIndex<&T, Hash, Invoice> {
+        ProofMapIndex::new("cryptocurrency.invoices", &self.view)
+    }
+
+    /// Returns invoice for the given id.
+    pub fn invoice(&self, hash: &Hash) -> Option<Invoice> {
+        self.invoices().get(hash)
+    }
+
+    /// Returns ids of the subscriptions whose next payment is due at the given height.
+    pub fn due_subscriptions(&self, height: Height) -> ListIndex<&T, Hash> {
+        ListIndex::new_in_family("cryptocurrency.due_subscriptions", &height.0, &self.view)
+    }
+
+    /// Returns ids of the unpaid invoices of the payee's wallet.
+    pub fn payee_invoices(&self, hash: &Hash) -> ListIndex<&T, Hash> {
+        ListIndex::new_in_family("cryptocurrency.payee_invoices", hash, &self.view)
+    }
+
+    /// Returns `ProofMapIndex` with assets.
+    pub fn assets(&self) -> ProofMapIndex<&T, Hash, Asset> {
+        ProofMapIndex::new("cryptocurrency.assets", &self.view)
+    }
+
+    /// Returns asset with the given name.
+    pub fn asset(&self, name: &str) -> Option<Asset> {
+        self.assets().get(&crypto::hash(name.as_bytes()))
+    }
+
+    /// Returns `ProofMapIndex` with balances of the assets keyed by the wallet and the asset.
+    pub fn asset_balances(&self) -> ProofMapIndex<&T, Hash, u64> {
+        ProofMapIndex::new("cryptocurrency.asset_balances", &self.view)
+    }
+
+    /// Returns balance of the wallet in the given asset.
+    pub fn balance(&self, wallet: &Wallet, asset: &str) -> u64 {
+        if asset == NATIVE_ASSET {
+            wallet.balance
+        } else {
+            self.asset_balances()
+                .get(&asset_balance_key(&wallet.name, asset))
+                .unwrap_or_default()
+        }
+    }
+
+    /// Returns `ProofMapIndex` with fees paid for the transactions keyed by the transaction hash.
+    pub fn fees(&self) -> ProofMapIndex<&T, Hash, u64> {
+        ProofMapIndex::new("cryptocurrency.fees", &self.view)
+    }
+
+    /// Returns fee paid for the given transaction.
+    pub fn fee(&self, transaction: &Hash) -> u64 {
+        self.fees().get(transaction).unwrap_or_default()
+    }
+
+    /// Returns `ProofMapIndex` with the events recorded in the wallet histories keyed by
+    /// the hash of the event.
+    pub fn wallet_events(&self) -> ProofMapIndex<&T, Hash, WalletEvent> {
+        ProofMapIndex::new("cryptocurrency.wallet_events", &self.view)
+    }
+
+    /// Returns wallet event for the given hash.
+    pub fn wallet_event(&self, hash: &Hash) -> Option<WalletEvent> {
+        self.wallet_events().get(hash)
+    }
+
     /// Returns the state hash of cryptocurrency service.
+    ///
+    /// Tables are listed in the order of the `*_TABLE` indices defined below.
     pub fn state_hash(&self) -> Vec<Hash> {
-        vec![self.wallets().merkle_root()]
+        vec![
+            self.wallets().merkle_root(),
+            self.transfer_proposals().merkle_root(),
+            self.transfer_proposals_archive().merkle_root(),
+            self.total_supply().hash(),
+            self.pending_operations().merkle_root(),
+            self.spending_limits().merkle_root(),
+            self.htlcs().merkle_root(),
+            self.allowances().merkle_root(),
+            self.subscriptions().merkle_root(),
+            self.invoices().merkle_root(),
+            self.assets().merkle_root(),
+            self.asset_balances().merkle_root(),
+            self.fees().merkle_root(),
+            self.key_wallets().merkle_root(),
+            self.wallet_proposals().merkle_root(),
+            self.wallet_events().merkle_root(),
+        ]
     }
 }
 
+/// Index of the wallets table in the state hash.
+pub const WALLETS_TABLE: usize = 0;
+/// Index of the pending transfer proposals table in the state hash.
+pub const TRANSFER_PROPOSALS_TABLE: usize = 1;
+/// Index of the archived transfer proposals table in the state hash.
+pub const TRANSFER_PROPOSALS_ARCHIVE_TABLE: usize = 2;
+/// Index of the total supply table in the state hash.
+pub const TOTAL_SUPPLY_TABLE: usize = 3;
+/// Index of the pending operations table in the state hash.
+pub const PENDING_OPERATIONS_TABLE: usize = 4;
+/// Index of the spending limits table in the state hash.
+pub const SPENDING_LIMITS_TABLE: usize = 5;
+/// Index of the HTLCs table in the state hash.
+pub const HTLCS_TABLE: usize = 6;
+/// Index of the allowances table in the state hash.
+pub const ALLOWANCES_TABLE: usize = 7;
+/// Index of the subscriptions table in the state hash.
+pub const SUBSCRIPTIONS_TABLE: usize = 8;
+/// Index of the invoices table in the state hash.
+pub const INVOICES_TABLE: usize = 9;
+/// Index of the assets table in the state hash.
+pub const ASSETS_TABLE: usize = 10;
+/// Index of the asset balances table in the state hash.
+pub const ASSET_BALANCES_TABLE: usize = 11;
+/// Index of the collected fees table in the state hash.
+pub const FEES_TABLE: usize = 12;
+/// Index of the wallet names by owner key table in the state hash.
+pub const KEY_WALLETS_TABLE: usize = 13;
+/// Index of the transfer proposal ids by wallet table in the state hash.
+pub const WALLET_PROPOSALS_TABLE: usize = 14;
+/// Index of the wallet events table in the state hash.
+pub const WALLET_EVENTS_TABLE: usize = 15;
+
 /// Implementation of mutable methods.
 impl<'a> Schema<&'a mut Fork> {
     /// Returns mutable `ProofMapIndex` with wallets.
@@ -86,6 +329,187 @@ impl<'a> Schema<&'a mut Fork> {
         ProofMapIndex::new("cryptocurrency.transfer_proposals", &mut self.view)
     }
 
+    /// Returns mutable `ProofMapIndex` with finished transfer proposals.
+    pub fn transfer_proposals_archive_mut(&mut self) -> ProofMapIndex<&mut Fork, Hash, TransferProposal> {
+        ProofMapIndex::new("cryptocurrency.transfer_proposals_archive", &mut self.view)
+    }
+
+    /// Returns mutable ids of the transfer proposals which can be approved until the given height.
+    pub fn expiring_proposals_mut(&mut self, height: Height) -> ListIndex<&mut Fork, Hash> {
+        ListIndex::new_in_family("cryptocurrency.expiring_proposals", &height.0, &mut self.view)
+    }
+
+    /// Returns mutable ids of the transfer proposals scheduled for execution at the given height.
+    pub fn scheduled_proposals_mut(&mut self, height: Height) -> ListIndex<&mut Fork, Hash> {
+        ListIndex::new_in_family("cryptocurrency.scheduled_proposals", &height.0, &mut self.view)
+    }
+
+    /// Returns mutable ids of the pending operations of the wallet with the given name hash.
+    pub fn wallet_operations_mut(&mut self, wallet: &Hash) -> ListIndex<&mut Fork, Hash> {
+        ListIndex::new_in_family("cryptocurrency.wallet_operations", wallet, &mut self.view)
+    }
+
+    /// Returns mutable ids of the pending operations which can be approved until the given height.
+    pub fn expiring_operations_mut(&mut self, height: Height) -> ListIndex<&mut Fork, Hash> {
+        ListIndex::new_in_family("cryptocurrency.expiring_operations", &height.0, &mut self.view)
+    }
+
+    /// Returns mutable ids of the transfer proposals made to pay the invoice with the given id.
+    pub fn invoice_proposals_mut(&mut self, invoice_id: &Hash) -> ListIndex<&mut Fork, Hash> {
+        ListIndex::new_in_family("cryptocurrency.invoice_proposals", invoice_id, &mut self.view)
+    }
+
+    /// Returns mutable `Entry` with the total amount of currency.
+    pub fn total_supply_mut(&mut self) -> Entry<&mut Fork, u64> {
+        Entry::new("cryptocurrency.total_supply", &mut self.view)
+    }
+
+    /// Returns mutable `ProofMapIndex` with wallet operations awaiting the quorum.
+    pub fn pending_operations_mut(&mut self) -> ProofMapIndex<&mut Fork, Hash, PendingOperation> {
+        ProofMapIndex::new("cryptocurrency.pending_operations", &mut self.view)
+    }
+
+    /// Returns mutable `ProofMapIndex` with spending limits of the wallets.
+    pub fn spending_limits_mut(&mut self) -> ProofMapIndex<&mut Fork, Hash, SpendingLimit> {
+        ProofMapIndex::new("cryptocurrency.spending_limits", &mut self.view)
+    }
+
+    /// Returns mutable `ProofMapIndex` with hash time-locked contracts.
+    pub fn htlcs_mut(&mut self) -> ProofMapIndex<&mut Fork, Hash, Htlc> {
+        ProofMapIndex::new("cryptocurrency.htlcs", &mut self.view)
+    }
+
+    /// Returns mutable `ProofMapIndex` with allowances.
+    pub fn allowances_mut(&mut self) -> ProofMapIndex<&mut Fork, Hash, Allowance> {
+        ProofMapIndex::new("cryptocurrency.allowances", &mut self.view)
+    }
+
+    /// Returns mutable `ProofMapIndex` with subscriptions.
+    pub fn subscriptions_mut(&mut self) -> ProofMapIndex<&mut Fork, Hash, Subscription> {
+        ProofMapIndex::new("cryptocurrency.subscriptions", &mut self.view)
+    }
+
+    /// Returns mutable ids of the subscriptions whose next payment is due at the given height.
+    pub fn due_subscriptions_mut(&mut self, height: Height) -> ListIndex<&mut Fork, Hash> {
+        ListIndex::new_in_family("cryptocurrency.due_subscriptions", &height.0, &mut self.view)
+    }
+
+    /// Returns mutable `ProofMapIndex` with invoices.
+    pub fn invoices_mut(&mut self) -> ProofMapIndex<&mut Fork, Hash, Invoice> {
+        ProofMapIndex::new("cryptocurrency.invoices", &mut self.view)
+    }
+
+    /// Returns mutable ids of the unpaid invoices of the payee's wallet.
+    pub fn payee_invoices_mut(&mut self, hash: &Hash) -> ListIndex<&mut Fork, Hash> {
+        ListIndex::new_in_family("cryptocurrency.payee_invoices", hash, &mut self.view)
+    }
+
+    /// Returns mutable `ProofMapIndex` with assets.
+    pub fn assets_mut(&mut self) -> ProofMapIndex<&mut Fork, Hash, Asset> {
+        ProofMapIndex::new("cryptocurrency.assets", &mut self.view)
+    }
+
+    /// Returns mutable `ProofMapIndex` with balances of the assets.
+    pub fn asset_balances_mut(&mut self) -> ProofMapIndex<&mut Fork, Hash, u64> {
+        ProofMapIndex::new("cryptocurrency.asset_balances", &mut self.view)
+    }
+
+    /// Returns mutable `ProofMapIndex` with fees paid for the transactions.
+    pub fn fees_mut(&mut self) -> ProofMapIndex<&mut Fork, Hash, u64> {
+        ProofMapIndex::new("cryptocurrency.fees", &mut self.view)
+    }
+
+    /// Returns mutable `ProofMapIndex` with the events recorded in the wallet histories.
+    pub fn wallet_events_mut(&mut self) -> ProofMapIndex<&mut Fork, Hash, WalletEvent> {
+        ProofMapIndex::new("cryptocurrency.wallet_events", &mut self.view)
+    }
+
+    /// Store the event and return its hash, which is recorded in the wallet histories.
+    pub fn put_wallet_event(&mut self, event: WalletEvent) -> Hash {
+        let hash = event.hash();
+        self.wallet_events_mut().put(&hash, event);
+        hash
+    }
+
+    /// Returns mutable `ProofMapIndex` with the hashes of the lists of pending transfer proposals.
+    pub fn wallet_proposals_mut(&mut self) -> ProofMapIndex<&mut Fork, Hash, Hash> {
+        ProofMapIndex::new("cryptocurrency.wallet_proposals", &mut self.view)
+    }
+
+    /// Returns mutable ids of the pending transfer proposals from the wallet.
+    pub fn wallet_proposal_ids_mut(&mut self, hash: &Hash) -> ProofListIndex<&mut Fork, Hash> {
+        ProofListIndex::new_in_family("cryptocurrency.wallet_proposal_ids", hash, &mut self.view)
+    }
+
+    /// Add the transfer proposal to the pending proposals of the sender's wallet.
+    fn add_wallet_proposal(&mut self, wallet: &str, id: &Hash) {
+        let hash = crypto::hash(wallet.as_bytes());
+        let ids_hash = {
+            let mut ids = self.wallet_proposal_ids_mut(&hash);
+            ids.push(*id);
+            ids.merkle_root()
+        };
+        self.wallet_proposals_mut().put(&hash, ids_hash);
+    }
+
+    /// Remove the transfer proposal from the pending proposals of the sender's wallet.
+    fn remove_wallet_proposal(&mut self, wallet: &str, id: &Hash) {
+        let hash = crypto::hash(wallet.as_bytes());
+        if !self.wallet_proposal_ids(&hash).iter().any(|pending| pending == *id) {
+            return;
+        }
+        let ids_hash = {
+            let mut ids = self.wallet_proposal_ids_mut(&hash);
+            let pending = ids.iter().filter(|pending| pending != id).collect::<Vec<_>>();
+            ids.clear();
+            ids.extend(pending);
+            ids.merkle_root()
+        };
+        if self.wallet_proposal_ids(&hash).is_empty() {
+            self.wallet_proposals_mut().remove(&hash);
+        } else {
+            self.wallet_proposals_mut().put(&hash, ids_hash);
+        }
+    }
+
+    /// Returns mutable `ProofMapIndex` with the hashes of the lists of wallet names.
+    pub fn key_wallets_mut(&mut self) -> ProofMapIndex<&mut Fork, PublicKey, Hash> {
+        ProofMapIndex::new("cryptocurrency.key_wallets", &mut self.view)
+    }
+
+    /// Returns mutable names of the wallets owned by the given public key.
+    pub fn key_wallet_names_mut(&mut self, key: &PublicKey) -> ProofListIndex<&mut Fork, String> {
+        ProofListIndex::new_in_family("cryptocurrency.key_wallet_names", key, &mut self.view)
+    }
+
+    /// Add the wallet to the names of the wallets owned by the given public key.
+    fn add_key_wallet(&mut self, key: &PublicKey, name: &str) {
+        let names_hash = {
+            let mut names = self.key_wallet_names_mut(key);
+            if !names.iter().any(|owned| owned == name) {
+                names.push(name.to_owned());
+            }
+            names.merkle_root()
+        };
+        self.key_wallets_mut().put(key, names_hash);
+    }
+
+    /// Remove the wallet from the names of the wallets owned by the given public key.
+    fn remove_key_wallet(&mut self, key: &PublicKey, name: &str) {
+        let names_hash = {
+            let mut names = self.key_wallet_names_mut(key);
+            let owned = names.iter().filter(|owned| owned != name).collect::<Vec<_>>();
+            names.clear();
+            names.extend(owned);
+            names.merkle_root()
+        };
+        if self.key_wallet_names(key).is_empty() {
+            self.key_wallets_mut().remove(key);
+        } else {
+            self.key_wallets_mut().put(key, names_hash);
+        }
+    }
+
     /// Returns history for the wallet by the given public key.
     pub fn wallet_history_mut(
         &mut self,
@@ -98,52 +522,468 @@ impl<'a> Schema<&'a mut Fork> {
     ///
     /// Panics if there is no wallet with given public key.
     pub fn increase_wallet_balance(&mut self, wallet: Wallet, amount: u64, transaction: &Hash) {
-        let wallet = {
-            let mut history = self.wallet_history_mut(&crypto::hash(wallet.name.as_bytes()));
-            history.push(*transaction);
-            let history_hash = history.merkle_root();
-            let balance = wallet.balance;
-            wallet.set_balance(balance + amount, &history_hash)
-        };
-        self.wallets_mut().put(&crypto::hash(wallet.name.as_bytes()), wallet.clone());
+        let balance = wallet.balance + amount;
+        self.set_wallet_balance(wallet, balance, transaction);
     }
 
     /// Decrease balance of the wallet and append new record to its history.
     ///
     /// Panics if there is no wallet with given public key.
     pub fn decrease_wallet_balance(&mut self, wallet: Wallet, amount: u64, transaction: &Hash) {
+        let balance = wallet.balance - amount;
+        self.set_wallet_balance(wallet, balance, transaction);
+    }
+
+    /// Set balance of the wallet and append the transaction to its history.
+    fn set_wallet_balance(&mut self, wallet: Wallet, balance: u64, transaction: &Hash) {
+        let wallet = match self.push_wallet_history(&wallet.name, transaction) {
+            Some(history_hash) => wallet.set_balance(balance, &history_hash),
+            None => Wallet { balance, ..wallet },
+        };
+        self.wallets_mut().put(&crypto::hash(wallet.name.as_bytes()), wallet);
+    }
+
+    /// Append the record to the history of the wallet and return the new hash of the
+    /// history, or `None` if the record is already the latest one there, e.g. when the
+    /// transaction also pays a fee.
+    fn push_wallet_history(&mut self, wallet: &str, record: &Hash) -> Option<Hash> {
+        let mut history = self.wallet_history_mut(&crypto::hash(wallet.as_bytes()));
+        if history.last() == Some(*record) {
+            return None;
+        }
+        history.push(*record);
+        Some(history.merkle_root())
+    }
+
+    /// Increase balance of the wallet in the given asset and append new record to its history.
+    pub fn increase_balance(&mut self, wallet: Wallet, asset: &str, amount: u64, transaction: &Hash) {
+        if asset == NATIVE_ASSET {
+            self.increase_wallet_balance(wallet, amount, transaction);
+        } else {
+            let key = asset_balance_key(&wallet.name, asset);
+            // Balances never exceed the supply of the asset, which is checked on issue.
+            let balance = self.asset_balances().get(&key).unwrap_or_default();
+            let balance = balance.checked_add(amount).expect("Asset balance overflow");
+            self.asset_balances_mut().put(&key, balance);
+            self.append_wallet_history(wallet, transaction);
+        }
+    }
+
+    /// Decrease balance of the wallet in the given asset and append new record to its history.
+    pub fn decrease_balance(&mut self, wallet: Wallet, asset: &str, amount: u64, transaction: &Hash) {
+        if asset == NATIVE_ASSET {
+            self.decrease_wallet_balance(wallet, amount, transaction);
+        } else {
+            let key = asset_balance_key(&wallet.name, asset);
+            let balance = self.asset_balances().get(&key).unwrap_or_default();
+            self.asset_balances_mut().put(&key, balance - amount);
+            self.append_wallet_history(wallet, transaction);
+        }
+    }
+
+    /// Append new record to the wallet history without changing its balance.
+    pub fn append_wallet_history(&mut self, wallet: Wallet, record: &Hash) {
+        if let Some(history_hash) = self.push_wallet_history(&wallet.name, record) {
+            let wallet = wallet.set_history_hash(&history_hash);
+            self.wallets_mut().put(&crypto::hash(wallet.name.as_bytes()), wallet);
+        }
+    }
+
+    /// Change owners and quorum of the wallet and append new record to its history.
+    pub fn change_wallet_owners(
+        &mut self,
+        wallet: Wallet,
+        pub_keys: Vec<PublicKey>,
+        weights: Vec<u32>,
+        quorum: u32,
+        quorum_tiers: Vec<QuorumTier>,
+        transaction: &Hash,
+    ) {
+        for key in wallet.pub_keys.iter().filter(|key| !pub_keys.contains(key)) {
+            self.remove_key_wallet(key, &wallet.name);
+        }
+        for key in pub_keys.iter().filter(|key| !wallet.pub_keys.contains(key)) {
+            self.add_key_wallet(key, &wallet.name);
+        }
+        // Approvals collected under the previous owners are no longer valid.
+        self.remove_wallet_operations(&wallet.name);
+
         let wallet = {
             let mut history = self.wallet_history_mut(&crypto::hash(wallet.name.as_bytes()));
             history.push(*transaction);
             let history_hash = history.merkle_root();
-            let balance = wallet.balance;
-            wallet.set_balance(balance - amount, &history_hash)
+            wallet.set_owners(pub_keys, weights, quorum, quorum_tiers, &history_hash)
         };
-        self.wallets_mut().put(&crypto::hash(wallet.name.as_bytes()), wallet.clone());
+        self.wallets_mut().put(&crypto::hash(wallet.name.as_bytes()), wallet);
     }
 
-    /// Create new wallet and append first record to its history.
-    pub fn create_wallet(&mut self, name: &String, keys: &Vec<PublicKey>, quorum: u32, transaction: &Hash) {
+    /// Create new wallet with the given balance and append first record to its history.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_wallet(
+        &mut self,
+        name: &String,
+        keys: &Vec<PublicKey>,
+        weights: &[u32],
+        quorum: u32,
+        quorum_tiers: &[QuorumTier],
+        balance: u64,
+        transaction: &Hash,
+    ) {
         let wallet = {
             let mut history = self.wallet_history_mut(&crypto::hash(name.as_bytes()));
             history.push(*transaction);
             let history_hash = history.merkle_root();
-            Wallet::new(name, keys.to_vec(), quorum, INITIAL_BALANCE, history.len(), &history_hash)
+            Wallet::new(
+                name,
+                keys.to_vec(),
+                weights.to_vec(),
+                quorum,
+                quorum_tiers.to_vec(),
+                balance,
+                history.len(),
+                &history_hash,
+            )
         };
         self.wallets_mut().put(&crypto::hash(name.as_bytes()), wallet);
+        for key in keys {
+            self.add_key_wallet(key, name);
+        }
+        self.increase_total_supply(balance);
+    }
+
+    /// Increase the total amount of currency.
+    ///
+    /// Transactions must check that the new supply fits into `u64` beforehand.
+    pub fn increase_total_supply(&mut self, amount: u64) {
+        let supply = self.total_supply().get().unwrap_or_default();
+        let supply = supply.checked_add(amount).expect("Total supply overflow");
+        self.total_supply_mut().set(supply);
+    }
+
+    /// Decrease the total amount of currency.
+    pub fn decrease_total_supply(&mut self, amount: u64) {
+        let supply = self.total_supply().get().unwrap_or_default();
+        self.total_supply_mut().set(supply - amount);
+    }
+
+    /// Create new pending operation with the first approval.
+    pub fn create_pending_operation(&mut self, id: &Hash, operation: PendingOperation) {
+        self.wallet_operations_mut(&crypto::hash(operation.wallet.as_bytes())).push(*id);
+        self.expiring_operations_mut(operation.valid_until).push(*id);
+        self.pending_operations_mut().put(id, operation);
+    }
+
+    /// Store the approvals collected for the wallet operation.
+    pub fn put_pending_operation(&mut self, id: &Hash, operation: PendingOperation) {
+        self.pending_operations_mut().put(id, operation);
+    }
+
+    /// Remove the wallet operation which has collected the quorum or expired, along with
+    /// its id in the pending operations of the wallet.
+    pub fn remove_pending_operation(&mut self, id: &Hash) {
+        if let Some(operation) = self.pending_operation(id) {
+            let mut ids = self.wallet_operations_mut(&crypto::hash(operation.wallet.as_bytes()));
+            let pending = ids.iter().filter(|pending| pending != id).collect::<Vec<_>>();
+            ids.clear();
+            ids.extend(pending);
+        }
+        self.pending_operations_mut().remove(id);
+    }
+
+    /// Remove all pending operations of the wallet.
+    pub fn remove_wallet_operations(&mut self, wallet: &str) {
+        let wallet_hash = crypto::hash(wallet.as_bytes());
+        let ids = self.wallet_operations(&wallet_hash).iter().collect::<Vec<_>>();
+        for id in ids {
+            self.pending_operations_mut().remove(&id);
+        }
+        self.wallet_operations_mut(&wallet_hash).clear();
+    }
+
+    /// Remove pending operations which can no longer be approved after the given height.
+    pub fn expire_pending_operations(&mut self, height: Height) {
+        let expired = self
+            .expiring_operations(height)
+            .iter()
+            .filter(|id| {
+                self.pending_operation(id)
+                    .map_or(false, |operation| operation.is_expired_at(height.next()))
+            })
+            .collect::<Vec<_>>();
+        for id in expired {
+            self.remove_pending_operation(&id);
+        }
+        self.expiring_operations_mut(height).clear();
+    }
+
+    /// Set the spending limit of the wallet or remove it if `limit` is `None`.
+    pub fn set_spending_limit(&mut self, wallet: &str, limit: Option<SpendingLimit>) {
+        let hash = crypto::hash(wallet.as_bytes());
+        match limit {
+            Some(limit) => self.spending_limits_mut().put(&hash, limit),
+            None => self.spending_limits_mut().remove(&hash),
+        }
+    }
+
+    /// Record the amount sent from the wallet if it has a spending limit.
+    pub fn record_spending(&mut self, wallet: &str, amount: u64, height: Height) {
+        let hash = crypto::hash(wallet.as_bytes());
+        if let Some(limit) = self.spending_limit(&hash) {
+            self.spending_limits_mut().put(&hash, limit.add_spending(amount, height));
+        }
+    }
+
+    /// Set the allowance of the spender's wallet on the owner's wallet, removing it
+    /// if the allowed amount is zero.
+    pub fn set_allowance(&mut self, allowance: Allowance) {
+        let key = allowance_key(&allowance.owner, &allowance.spender);
+        if allowance.amount == 0 {
+            self.allowances_mut().remove(&key);
+        } else {
+            self.allowances_mut().put(&key, allowance);
+        }
+    }
+
+    /// Transfer funds from the owner's wallet to the receiver's wallet within the allowance.
+    pub fn transfer_from(&mut self, allowance: Allowance, owner: Wallet, receiver: Wallet, amount: u64, transaction: &Hash) {
+        self.decrease_wallet_balance(owner, amount, transaction);
+        self.increase_wallet_balance(receiver, amount, transaction);
+        self.set_allowance(allowance.spend(amount));
+    }
+
+    /// Lock funds of the sender's wallet under the given id.
+    pub fn create_htlc(&mut self, id: &Hash, htlc: Htlc, sender: Wallet, transaction: &Hash) {
+        self.decrease_wallet_balance(sender, htlc.amount, transaction);
+        self.htlcs_mut().put(id, htlc);
+    }
+
+    /// Credit the locked funds to the receiver's wallet.
+    pub fn claim_htlc(&mut self, id: &Hash, htlc: Htlc, preimage: &[u8], receiver: Wallet, transaction: &Hash) {
+        self.increase_wallet_balance(receiver, htlc.amount, transaction);
+        self.htlcs_mut().put(id, htlc.claim(preimage));
+    }
+
+    /// Return the locked funds to the sender's wallet.
+    pub fn refund_htlc(&mut self, id: &Hash, htlc: Htlc, sender: Wallet, transaction: &Hash) {
+        self.increase_wallet_balance(sender, htlc.amount, transaction);
+        self.htlcs_mut().put(id, htlc.refund());
     }
 
     /// Create new transfer proposal.
-    pub fn create_transfer_proposal(&mut self, from: &String, to: &String, amount: u64, seed: u64) {
-        let transfer_proposal = TransferProposal::new(from, to, amount, seed, 1);
-        self.transfer_proposals_mut().put(&transfer_proposal.hash(), transfer_proposal);
+    pub fn create_transfer_proposal(&mut self, id: &Hash, transfer_proposal: TransferProposal) {
+        self.add_wallet_proposal(&transfer_proposal.from, id);
+        self.expiring_proposals_mut(transfer_proposal.valid_until).push(*id);
+        if transfer_proposal.invoice_id != Hash::zero() {
+            self.invoice_proposals_mut(&transfer_proposal.invoice_id).push(*id);
+        }
+        self.transfer_proposals_mut().put(id, transfer_proposal);
     }
 
-    /// Increase proposal signs
-    pub fn increase_proposal_signs(&mut self, transfer_proposal: TransferProposal) {
+    /// Add approval of the given key to the transfer proposal.
+    pub fn approve_transfer_proposal(&mut self, id: &Hash, transfer_proposal: TransferProposal, key: &PublicKey) {
         let transfer_proposal = {
-            transfer_proposal.increase_signs()
+            transfer_proposal.add_approval(key)
         };
-        self.transfer_proposals_mut().put(&transfer_proposal.hash(), transfer_proposal.clone());
+        self.transfer_proposals_mut().put(id, transfer_proposal);
+    }
+
+    /// Add rejection of the given key to the transfer proposal.
+    pub fn reject_transfer_proposal(&mut self, id: &Hash, transfer_proposal: TransferProposal, key: &PublicKey) {
+        let transfer_proposal = {
+            transfer_proposal.add_rejection(key)
+        };
+        self.transfer_proposals_mut().put(id, transfer_proposal);
+    }
+
+    /// Finish the transfer proposal with the given status and move it to the archive.
+    pub fn archive_transfer_proposal(&mut self, id: &Hash, transfer_proposal: TransferProposal, status: ProposalStatus) {
+        self.remove_wallet_proposal(&transfer_proposal.from, id);
+        self.transfer_proposals_mut().remove(id);
+        self.transfer_proposals_archive_mut().put(id, transfer_proposal.set_status(status));
+    }
+
+    /// Reserve the amount of the approved transfer proposal from the sender's wallet until
+    /// the execution height of the proposal.
+    pub fn schedule_transfer_proposal(&mut self, id: &Hash, transfer_proposal: TransferProposal, sender: Wallet, transaction: &Hash) {
+        self.decrease_balance(sender, &transfer_proposal.asset, transfer_proposal.amount, transaction);
+        self.remove_wallet_proposal(&transfer_proposal.from, id);
+        self.scheduled_proposals_mut(transfer_proposal.execute_at).push(*id);
+        self.transfer_proposals_mut().put(id, transfer_proposal.set_status(ProposalStatus::Scheduled));
+    }
+
+    /// Return the reserved amount of the scheduled transfer proposal to the sender's wallet
+    /// and archive the proposal as cancelled.
+    pub fn cancel_scheduled_transfer_proposal(&mut self, id: &Hash, transfer_proposal: TransferProposal, transaction: &Hash) {
+        if let Some(sender) = self.wallet(&crypto::hash(transfer_proposal.from.as_bytes())) {
+            self.increase_balance(sender, &transfer_proposal.asset, transfer_proposal.amount, transaction);
+        }
+        self.archive_transfer_proposal(id, transfer_proposal, ProposalStatus::Cancelled);
+    }
+
+    /// Move the reserved amounts of scheduled transfer proposals which are due at the given
+    /// height to the receivers' wallets and record the execution in their histories.
+    pub fn execute_scheduled_transfers(&mut self, height: Height) {
+        // The index also keeps the ids of the proposals cancelled before their execution.
+        let due = self
+            .scheduled_proposals(height)
+            .iter()
+            .filter_map(|id| self.transfer_proposal(&id).map(|proposal| (id, proposal)))
+            .filter(|(_, proposal)| proposal.is_due_at(height))
+            .collect::<Vec<_>>();
+
+        for (id, proposal) in due {
+            if let Some(receiver) = self.wallet(&crypto::hash(proposal.to.as_bytes())) {
+                let event = WalletEvent::new(
+                    WalletEventKind::ScheduledTransferExecuted,
+                    &id,
+                    height,
+                    proposal.amount,
+                );
+                let event = self.put_wallet_event(event);
+                self.increase_balance(receiver, &proposal.asset, proposal.amount, &event);
+            }
+            self.archive_transfer_proposal(&id, proposal, ProposalStatus::Executed);
+        }
+        self.scheduled_proposals_mut(height).clear();
+    }
+
+    /// Archive pending transfer proposals which can no longer be approved after the
+    /// given height and record their expiry in the history of the sender's wallet.
+    pub fn expire_transfer_proposals(&mut self, height: Height) {
+        // The index also keeps the ids of the proposals finished before their expiry.
+        let expired = self
+            .expiring_proposals(height)
+            .iter()
+            .filter_map(|id| self.transfer_proposal(&id).map(|proposal| (id, proposal)))
+            .filter(|(_, proposal)| proposal.is_expired_at(height.next()))
+            .collect::<Vec<_>>();
+
+        for (id, proposal) in expired {
+            if let Some(wallet) = self.wallet(&crypto::hash(proposal.from.as_bytes())) {
+                let event = WalletEvent::new(WalletEventKind::ProposalExpired, &id, height, 0);
+                let event = self.put_wallet_event(event);
+                self.append_wallet_history(wallet, &event);
+            }
+            self.archive_transfer_proposal(&id, proposal, ProposalStatus::Expired);
+        }
+        self.expiring_proposals_mut(height).clear();
+    }
+
+    /// Create subscription under the given id.
+    pub fn create_subscription(&mut self, id: &Hash, subscription: Subscription) {
+        self.due_subscriptions_mut(subscription.next_payment).push(*id);
+        self.subscriptions_mut().put(id, subscription);
+    }
+
+    /// Remove subscription with the given id, so that no further payments are made.
+    pub fn cancel_subscription(&mut self, id: &Hash) {
+        if let Some(subscription) = self.subscription(id) {
+            let mut ids = self.due_subscriptions_mut(subscription.next_payment);
+            let due = ids.iter().filter(|due| due != id).collect::<Vec<_>>();
+            ids.clear();
+            ids.extend(due);
+        }
+        self.subscriptions_mut().remove(id);
+    }
+
+    /// Create new asset.
+    pub fn create_asset(&mut self, asset: Asset) {
+        self.assets_mut().put(&crypto::hash(asset.name.as_bytes()), asset);
+    }
+
+    /// Store the asset with the supply increased by the issued amount and credit this
+    /// amount to the receiver's wallet.
+    pub fn issue_asset(&mut self, asset: Asset, receiver: Wallet, amount: u64, transaction: &Hash) {
+        self.increase_balance(receiver, &asset.name, amount, transaction);
+        self.assets_mut().put(&crypto::hash(asset.name.as_bytes()), asset);
+    }
+
+    /// Move the fee for the transaction from the payer's wallet to the treasury wallet
+    /// and record it for the transaction.
+    pub fn pay_fee(&mut self, payer: Wallet, treasury: Wallet, fee: u64, transaction: &Hash) {
+        self.decrease_wallet_balance(payer, fee, transaction);
+        self.increase_wallet_balance(treasury, fee, transaction);
+
+        let paid = self.fee(transaction);
+        self.fees_mut().put(transaction, paid + fee);
+    }
+
+    /// Create invoice under the given id.
+    pub fn create_invoice(&mut self, id: &Hash, invoice: Invoice) {
+        self.payee_invoices_mut(&crypto::hash(invoice.payee.as_bytes())).push(*id);
+        self.invoices_mut().put(id, invoice);
+    }
+
+    /// Mark invoice with the given id as paid by the given transfer proposal, remove it
+    /// from the unpaid invoices of the payee and cancel the other pending proposals made
+    /// to pay it.
+    pub fn pay_invoice(&mut self, id: &Hash, invoice: Invoice, proposal_id: &Hash) {
+        {
+            let mut ids = self.payee_invoices_mut(&crypto::hash(invoice.payee.as_bytes()));
+            let unpaid = ids.iter().filter(|unpaid| unpaid != id).collect::<Vec<_>>();
+            ids.clear();
+            ids.extend(unpaid);
+        }
+        self.invoices_mut().put(id, invoice.pay());
+
+        // The index also keeps the ids of the proposals which have already finished.
+        let siblings = self
+            .invoice_proposals(id)
+            .iter()
+            .filter(|sibling| sibling != proposal_id)
+            .filter_map(|sibling| {
+                self.transfer_proposal(&sibling)
+                    .map(|proposal| (sibling, proposal))
+            })
+            .filter(|(_, proposal)| proposal.status == ProposalStatus::Pending)
+            .collect::<Vec<_>>();
+        for (sibling, proposal) in siblings {
+            self.archive_transfer_proposal(&sibling, proposal, ProposalStatus::Cancelled);
+        }
+        self.invoice_proposals_mut(id).clear();
+    }
+
+    /// Make the payments of subscriptions which are due at the given height. Payments
+    /// count toward the spending limit of the payer's wallet. Payments which exceed the
+    /// balance of the payer's wallet are skipped. Both are recorded as wallet events.
+    pub fn execute_subscriptions(&mut self, height: Height) {
+        let due = self
+            .due_subscriptions(height)
+            .iter()
+            .filter_map(|id| self.subscription(&id).map(|subscription| (id, subscription)))
+            .filter(|(_, subscription)| subscription.is_due_at(height))
+            .collect::<Vec<_>>();
+
+        for (id, subscription) in due {
+            let sender = self.wallet(&crypto::hash(subscription.from.as_bytes()));
+            let receiver = self.wallet(&crypto::hash(subscription.to.as_bytes()));
+            if let (Some(sender), Some(receiver)) = (sender, receiver) {
+                if sender.balance >= subscription.amount {
+                    let event = WalletEvent::new(
+                        WalletEventKind::SubscriptionPaid,
+                        &id,
+                        height,
+                        subscription.amount,
+                    );
+                    let event = self.put_wallet_event(event);
+                    self.record_spending(&subscription.from, subscription.amount, height);
+                    self.decrease_wallet_balance(sender, subscription.amount, &event);
+                    self.increase_wallet_balance(receiver, subscription.amount, &event);
+                } else {
+                    let event = WalletEvent::new(WalletEventKind::SubscriptionSkipped, &id, height, 0);
+                    let event = self.put_wallet_event(event);
+                    self.append_wallet_history(sender, &event);
+                }
+            }
+
+            match subscription.advance() {
+                Some(subscription) => {
+                    self.due_subscriptions_mut(subscription.next_payment).push(id);
+                    self.subscriptions_mut().put(&id, subscription);
+                }
+                None => self.subscriptions_mut().remove(&id),
+            }
+        }
+        self.due_subscriptions_mut(height).clear();
     }
 }
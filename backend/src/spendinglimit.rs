@@ -0,0 +1,74 @@
+//! Spending limit of a wallet
+
+use exonum::helpers::Height;
+
+use super::proto;
+
+/// Amount of currency sent from a wallet at the given height.
+#[derive(Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::Spending", serde_pb_convert)]
+pub struct Spending {
+    /// Height of the block with the transfer.
+    pub height: Height,
+    /// Amount of currency sent.
+    pub amount: u64,
+}
+
+/// Limit on the amount of currency which can be sent from a wallet with a lower quorum
+/// during a rolling window of blocks.
+#[derive(Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::SpendingLimit", serde_pb_convert)]
+pub struct SpendingLimit {
+    /// Amount of currency which can be sent during the window.
+    pub limit: u64,
+    /// Length of the window in blocks.
+    pub window: u64,
+    /// Quorum size for transfers within the limit.
+    pub quorum: u32,
+    /// Transfers from the wallet within the current window.
+    pub spendings: Vec<Spending>,
+}
+
+impl SpendingLimit {
+    /// Create new spending limit.
+    pub fn new(limit: u64, window: u64, quorum: u32, spendings: Vec<Spending>) -> Self {
+        Self {
+            limit,
+            window,
+            quorum,
+            spendings,
+        }
+    }
+    /// Returns `true` if the spending made at `spent_at` counts toward the limit at the
+    /// given height. Windows reaching past the maximum height never end.
+    fn is_in_window(&self, spent_at: Height, height: Height) -> bool {
+        spent_at.0.saturating_add(self.window) > height.0
+    }
+    /// Returns the amount of currency sent during the window ending at the given height.
+    pub fn spent_at(&self, height: Height) -> u64 {
+        self.spendings
+            .iter()
+            .filter(|spending| self.is_in_window(spending.height, height))
+            .map(|spending| spending.amount)
+            .sum()
+    }
+    /// Returns `true` if the given amount can be sent at the given height without
+    /// exceeding the limit.
+    pub fn allows(&self, amount: u64, height: Height) -> bool {
+        self.spent_at(height)
+            .checked_add(amount)
+            .map_or(false, |spent| spent <= self.limit)
+    }
+    /// Returns a copy of this limit with the given spending recorded and the spendings
+    /// outside of the window dropped.
+    pub fn add_spending(self, amount: u64, height: Height) -> Self {
+        let mut spendings = self
+            .spendings
+            .iter()
+            .filter(|spending| self.is_in_window(spending.height, height))
+            .cloned()
+            .collect::<Vec<_>>();
+        spendings.push(Spending { height, amount });
+        Self { spendings, ..self }
+    }
+}
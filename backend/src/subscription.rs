@@ -0,0 +1,62 @@
+//! Recurring payment from a wallet
+
+use exonum::helpers::Height;
+
+use super::proto;
+
+/// Standing order paying a fixed amount of currency from one wallet to another every
+/// `period_blocks`.
+#[derive(Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::Subscription", serde_pb_convert)]
+pub struct Subscription {
+    /// Name of payer's wallet.
+    pub from: String,
+    /// Name of payee's wallet.
+    pub to: String,
+    /// Amount of currency paid every period.
+    pub amount: u64,
+    /// Length of the period in blocks.
+    pub period_blocks: u64,
+    /// Number of payments left.
+    pub remaining: u64,
+    /// Height of the next payment.
+    pub next_payment: Height,
+}
+
+impl Subscription {
+    /// Create new subscription.
+    pub fn new(
+        from: &str,
+        to: &str,
+        amount: u64,
+        period_blocks: u64,
+        remaining: u64,
+        next_payment: Height,
+    ) -> Self {
+        Self {
+            from: from.to_owned(),
+            to: to.to_owned(),
+            amount,
+            period_blocks,
+            remaining,
+            next_payment,
+        }
+    }
+    /// Returns `true` if the next payment is due at the given height.
+    pub fn is_due_at(&self, height: Height) -> bool {
+        height >= self.next_payment
+    }
+    /// Returns a copy of this subscription moved to the next period, or `None` if no
+    /// payments are left or the next payment would be past the maximum height.
+    pub fn advance(self) -> Option<Self> {
+        if self.remaining <= 1 {
+            return None;
+        }
+        let next_payment = self.next_payment.0.checked_add(self.period_blocks)?;
+        Some(Self {
+            remaining: self.remaining - 1,
+            next_payment: Height(next_payment),
+            ..self
+        })
+    }
+}
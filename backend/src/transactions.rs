@@ -19,15 +19,27 @@
 #![allow(bare_trait_objects)]
 
 use exonum::{
-    blockchain::{ExecutionError, ExecutionResult, Transaction, TransactionContext},
+    blockchain::{self, ExecutionError, ExecutionResult, Transaction, TransactionContext},
     crypto,
-    crypto::{PublicKey, SecretKey},
-    messages::{Message, RawTransaction, Signed},
+    crypto::{CryptoHash, Hash, HashStream, PublicKey, SecretKey},
+    helpers::Height,
+    messages::{BinaryForm, Message, RawTransaction, Signed},
+    storage::{Fork, Snapshot},
 };
 
 use super::proto;
-use crate::{schema::Schema, CRYPTOCURRENCY_SERVICE_ID};
-use crate::transferproposal::TransferProposal;
+use crate::allowance::Allowance;
+use crate::asset::{Asset, NATIVE_ASSET};
+use crate::{
+    config::ServiceConfig, schema::Schema, CRYPTOCURRENCY_SERVICE_ID, MAX_MEMO_LENGTH,
+};
+use crate::htlc::{Htlc, HtlcStatus};
+use crate::invoice::{Invoice, InvoiceStatus};
+use crate::pendingoperation::PendingOperation;
+use crate::spendinglimit::SpendingLimit;
+use crate::subscription::Subscription;
+use crate::transferproposal::{ProposalStatus, TransferProposal};
+use crate::wallet::{QuorumTier, Wallet};
 
 const ERROR_SENDER_SAME_AS_RECEIVER: u8 = 0;
 const ERROR_SENDER_WRONG_KEY: u8 = 1;
@@ -44,19 +56,26 @@ pub enum Error {
 
     /// Sender doesn't exist.
     ///
-    /// Can be emitted by `Transfer`.
+    /// Can be emitted by `Transfer`, `ProposeTransfer`, `ApproveTransfer`, `RejectTransfer`,
+    /// `Burn`, `AddOwner`, `RemoveOwner`, `ReplaceOwner`, `ChangeQuorum`, `ChangeQuorumTiers`,
+    /// `SetSpendingLimit`, `CancelTransfer`, `HtlcLock`, `HtlcRefund`, `BatchTransfer`,
+    /// `Approve`, `TransferFrom`, `CreateSubscription`, `CancelSubscription`, `PayInvoice`
+    /// or `CreateAsset`.
     #[fail(display = "Sender doesn't exist")]
     SenderNotFound = 1,
 
     /// Receiver doesn't exist.
     ///
-    /// Can be emitted by `Transfer` or `Issue`.
+    /// Can be emitted by `Transfer`, `ProposeTransfer`, `ApproveTransfer`, `Issue`, `HtlcLock`,
+    /// `HtlcClaim`, `BatchTransfer`, `Approve`, `TransferFrom`, `CreateSubscription`,
+    /// `CreateInvoice` or `PayInvoice`.
     #[fail(display = "Receiver doesn't exist")]
     ReceiverNotFound = 2,
 
     /// Insufficient currency amount.
     ///
-    /// Can be emitted by `Transfer`.
+    /// Can be emitted by `Transfer`, `ProposeTransfer`, `ApproveTransfer`, `Burn`, `HtlcLock`,
+    /// `BatchTransfer`, `TransferFrom` or `PayInvoice`.
     #[fail(display = "Insufficient currency amount")]
     InsufficientCurrencyAmount = 3,
 
@@ -68,9 +87,216 @@ pub enum Error {
 
     /// Quorum number is not correct.
     ///
-    /// Can be emitted by `CreateWallet`.
+    /// Can be emitted by `CreateWallet`, `RemoveOwner`, `ChangeQuorum`, `ChangeQuorumTiers`
+    /// or `SetSpendingLimit`.
     #[fail(display = "Quorum is not correct")]
     QuorumIsNotCorrect = 5,
+
+    /// Transfer or wallet operation is already approved by this key.
+    ///
+    /// Can be emitted by `Transfer`, `ApproveTransfer`, `RejectTransfer`, `Burn`, `AddOwner`,
+    /// `RemoveOwner`, `ReplaceOwner`, `ChangeQuorum`, `ChangeQuorumTiers`, `SetSpendingLimit`,
+    /// `CancelTransfer`, `HtlcLock`, `BatchTransfer`, `Approve`, `TransferFrom`,
+    /// `CreateSubscription`, `CancelSubscription` or `CreateAsset`.
+    #[fail(display = "Already approved by this key")]
+    AlreadyApproved = 6,
+
+    /// Transfer proposal doesn't exist.
+    ///
+    /// Can be emitted by `ApproveTransfer`, `RejectTransfer` or `CancelTransfer`.
+    #[fail(display = "Transfer proposal doesn't exist")]
+    ProposalNotFound = 7,
+
+    /// Transfer is already rejected by this key.
+    ///
+    /// Can be emitted by `Transfer`, `ApproveTransfer` or `RejectTransfer`.
+    #[fail(display = "Transfer already rejected by this key")]
+    AlreadyRejected = 8,
+
+    /// Transfer proposal is already scheduled, executed, rejected, expired or cancelled.
+    ///
+    /// Can be emitted by `Transfer`, `ApproveTransfer`, `RejectTransfer` or `CancelTransfer`.
+    #[fail(display = "Transfer proposal is not pending")]
+    ProposalNotPending = 9,
+
+    /// Transfer proposal can no longer be approved.
+    ///
+    /// Can be emitted by `Transfer`, `ProposeTransfer`, `ApproveTransfer`, `RejectTransfer`
+    /// or `CancelTransfer`.
+    #[fail(display = "Transfer proposal has expired")]
+    ProposalExpired = 10,
+
+    /// Author of the transaction is not allowed to issue currency or the asset.
+    ///
+    /// Can be emitted by `Issue`.
+    #[fail(display = "Issuer is not authorized")]
+    UnauthorizedIssuer = 11,
+
+    /// Issued amount exceeds the maximum supply of the currency.
+    ///
+    /// Can be emitted by `Issue`.
+    #[fail(display = "Maximum supply exceeded")]
+    SupplyCapExceeded = 12,
+
+    /// Key is already one of the wallet owners.
+    ///
+    /// Can be emitted by `AddOwner`, `ReplaceOwner` or `CreateWallet`.
+    #[fail(display = "Key is already an owner of the wallet")]
+    OwnerAlreadyExists = 13,
+
+    /// Key is not one of the wallet owners.
+    ///
+    /// Can be emitted by `RemoveOwner` or `ReplaceOwner`.
+    #[fail(display = "Key is not an owner of the wallet")]
+    OwnerNotFound = 14,
+
+    /// Weights of the wallet owners are not correct.
+    ///
+    /// Can be emitted by `CreateWallet` or `AddOwner`.
+    #[fail(display = "Weights are not correct")]
+    WeightsNotCorrect = 15,
+
+    /// Quorum tiers are not ordered by the maximum amount.
+    ///
+    /// Can be emitted by `CreateWallet` or `ChangeQuorumTiers`.
+    #[fail(display = "Quorum tiers are not correct")]
+    QuorumTiersNotCorrect = 16,
+
+    /// Transfer approved by the lower quorum exceeds the spending limit of the wallet
+    /// and the full quorum can no longer be reached.
+    ///
+    /// Can be emitted by `Transfer` or `ApproveTransfer`.
+    #[fail(display = "Spending limit exceeded")]
+    LimitExceeded = 17,
+
+    /// Hash time-locked contract with the same id already exists.
+    ///
+    /// Can be emitted by `HtlcLock`.
+    #[fail(display = "Lock already exists")]
+    HtlcAlreadyExists = 18,
+
+    /// Hash time-locked contract doesn't exist.
+    ///
+    /// Can be emitted by `HtlcClaim` or `HtlcRefund`.
+    #[fail(display = "Lock doesn't exist")]
+    HtlcNotFound = 19,
+
+    /// Hash time-locked contract is already claimed or refunded.
+    ///
+    /// Can be emitted by `HtlcClaim` or `HtlcRefund`.
+    #[fail(display = "Lock is already claimed or refunded")]
+    HtlcNotLocked = 20,
+
+    /// Hash time-locked contract can no longer be claimed.
+    ///
+    /// Can be emitted by `HtlcLock` or `HtlcClaim`.
+    #[fail(display = "Lock has timed out")]
+    HtlcTimedOut = 21,
+
+    /// Hash time-locked contract can't be refunded before it times out.
+    ///
+    /// Can be emitted by `HtlcRefund`.
+    #[fail(display = "Lock has not timed out yet")]
+    HtlcNotTimedOut = 22,
+
+    /// Preimage doesn't match the hashlock.
+    ///
+    /// Can be emitted by `HtlcClaim`.
+    #[fail(display = "Preimage doesn't match the hashlock")]
+    InvalidPreimage = 23,
+
+    /// Batch transfer has no outputs.
+    ///
+    /// Can be emitted by `BatchTransfer`.
+    #[fail(display = "Batch transfer has no outputs")]
+    EmptyBatch = 24,
+
+    /// Transferred amount exceeds the allowance of the spender's wallet.
+    ///
+    /// Can be emitted by `TransferFrom`.
+    #[fail(display = "Allowance exceeded")]
+    AllowanceExceeded = 25,
+
+    /// Period or number of payments of the subscription is zero, or the last payment
+    /// is past the maximum height.
+    ///
+    /// Can be emitted by `CreateSubscription`.
+    #[fail(display = "Subscription is not correct")]
+    SubscriptionNotCorrect = 26,
+
+    /// Subscription with the same id already exists.
+    ///
+    /// Can be emitted by `CreateSubscription`.
+    #[fail(display = "Subscription already exists")]
+    SubscriptionAlreadyExists = 27,
+
+    /// Subscription doesn't exist or has already made all of its payments.
+    ///
+    /// Can be emitted by `CancelSubscription`.
+    #[fail(display = "Subscription doesn't exist")]
+    SubscriptionNotFound = 28,
+
+    /// Memo of the transfer is longer than allowed.
+    ///
+    /// Can be emitted by `Transfer`, `ProposeTransfer` or `CreateInvoice`.
+    #[fail(display = "Memo is too long")]
+    MemoTooLong = 29,
+
+    /// Invoice doesn't exist.
+    ///
+    /// Can be emitted by `PayInvoice`.
+    #[fail(display = "Invoice doesn't exist")]
+    InvoiceNotFound = 30,
+
+    /// Invoice can no longer be paid.
+    ///
+    /// Can be emitted by `CreateInvoice`, `PayInvoice` or `ApproveTransfer`.
+    #[fail(display = "Invoice has expired")]
+    InvoiceExpired = 31,
+
+    /// Invoice has already been paid.
+    ///
+    /// Can be emitted by `PayInvoice` or `ApproveTransfer`.
+    #[fail(display = "Invoice is already paid")]
+    InvoiceAlreadyPaid = 32,
+
+    /// Asset with the same name already exists.
+    ///
+    /// Can be emitted by `CreateAsset`.
+    #[fail(display = "Asset already exists")]
+    AssetAlreadyExists = 33,
+
+    /// Asset doesn't exist.
+    ///
+    /// Can be emitted by `Transfer`, `ProposeTransfer` or `Issue`.
+    #[fail(display = "Asset doesn't exist")]
+    AssetNotFound = 34,
+
+    /// Balance of the paying wallet is not enough to pay the fee.
+    ///
+    /// Can be emitted by `Transfer`, `ProposeTransfer`, `ApproveTransfer`, `RejectTransfer`,
+    /// `BatchTransfer`, `PayInvoice` or `CreateWallet`.
+    #[fail(display = "Insufficient funds to pay the fee")]
+    InsufficientFee = 35,
+
+    /// Name of the wallet or asset is longer than allowed.
+    ///
+    /// Can be emitted by `CreateWallet` or `CreateAsset`.
+    #[fail(display = "Name is too long")]
+    NameTooLong = 36,
+
+    /// Total supply of the currency or the asset would overflow.
+    ///
+    /// Can be emitted by `Issue` or `CreateWallet`.
+    #[fail(display = "Total supply overflow")]
+    SupplyOverflow = 37,
+
+    /// Treasury wallet which receives the fees doesn't exist.
+    ///
+    /// Can be emitted by `Transfer`, `ProposeTransfer`, `ApproveTransfer`, `RejectTransfer`,
+    /// `BatchTransfer`, `PayInvoice` or `CreateWallet`.
+    #[fail(display = "Treasury wallet doesn't exist")]
+    TreasuryNotFound = 38,
 }
 
 impl From<Error> for ExecutionError {
@@ -94,9 +320,17 @@ pub struct Transfer {
     ///
     /// [idempotence]: https://en.wikipedia.org/wiki/Idempotence
     pub seed: u64,
+    /// Reference of the transfer, such as an invoice id, empty if not set.
+    pub memo: String,
+    /// Name of the transferred asset, empty for the native currency.
+    pub asset: String,
 }
 
 /// Issue `amount` of the currency to the `wallet`.
+///
+/// The native currency is issued by one of the configured issuers. Other assets require
+/// the quorum of the owners of the asset issuer's wallet, each of whom submits the same
+/// transaction.
 #[derive(Serialize, Deserialize, Clone, Debug, ProtobufConvert)]
 #[exonum(pb = "proto::Issue")]
 pub struct Issue {
@@ -108,6 +342,24 @@ pub struct Issue {
     ///
     /// [idempotence]: https://en.wikipedia.org/wiki/Idempotence
     pub seed: u64,
+    /// Name of the issued asset, empty for the native currency.
+    pub asset: String,
+}
+
+/// Burn `amount` of the currency from the `wallet`.
+///
+/// Requires the quorum of the wallet owners, each of whom submits the same transaction.
+#[derive(Serialize, Deserialize, Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::Burn")]
+pub struct Burn {
+    /// Name of the wallet.
+    pub wallet: String,
+    /// Burned amount of currency.
+    pub amount: u64,
+    /// Auxiliary number to guarantee [non-idempotence][idempotence] of transactions.
+    ///
+    /// [idempotence]: https://en.wikipedia.org/wiki/Idempotence
+    pub seed: u64,
 }
 
 /// Create wallet with the given `name`.
@@ -118,8 +370,377 @@ pub struct CreateWallet {
     pub name: String,
     /// Wallet keys.
     pub pub_keys: Vec<PublicKey>,
-    /// Quorum size.
+    /// Quorum size, i.e. the total weight of approvals required.
+    pub quorum: u32,
+    /// Weights of the wallet keys, empty if every key has the weight of 1.
+    pub weights: Vec<u32>,
+    /// Quorums for smaller transfers, ordered by `max_amount`.
+    pub quorum_tiers: Vec<QuorumTier>,
+}
+
+/// Propose transfer of `amount` of the currency from a multisig wallet.
+///
+/// The hash of this transaction is the id of the created proposal.
+#[derive(Serialize, Deserialize, Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::ProposeTransfer")]
+pub struct ProposeTransfer {
+    /// Name of sender's wallet.
+    pub from: String,
+    /// Name of receiver's wallet.
+    pub to: String,
+    /// Amount of currency to transfer.
+    pub amount: u64,
+    /// Auxiliary number to guarantee [non-idempotence][idempotence] of transactions.
+    ///
+    /// [idempotence]: https://en.wikipedia.org/wiki/Idempotence
+    pub seed: u64,
+    /// Last height at which the proposal can be approved, `Height(0)` for the maximum lifetime.
+    pub valid_until: Height,
+    /// Height at which the approved transfer is executed, `Height(0)` to execute it immediately.
+    pub execute_at: Height,
+    /// Reference of the transfer, such as an invoice id, empty if not set.
+    pub memo: String,
+    /// Name of the transferred asset, empty for the native currency.
+    pub asset: String,
+}
+
+/// Approve the transfer proposal with the given id.
+#[derive(Serialize, Deserialize, Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::ApproveTransfer")]
+pub struct ApproveTransfer {
+    /// Id of the transfer proposal.
+    pub proposal_id: Hash,
+}
+
+/// Reject the transfer proposal with the given id.
+#[derive(Serialize, Deserialize, Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::RejectTransfer")]
+pub struct RejectTransfer {
+    /// Id of the transfer proposal.
+    pub proposal_id: Hash,
+}
+
+/// Cancel the pending or scheduled transfer proposal with the given id.
+///
+/// Requires the quorum of the sender's wallet owners, each of whom submits the same
+/// transaction.
+#[derive(Serialize, Deserialize, Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::CancelTransfer")]
+pub struct CancelTransfer {
+    /// Id of the transfer proposal.
+    pub proposal_id: Hash,
+}
+
+/// Add `key` to the owners of the `wallet`.
+///
+/// Requires the quorum of the wallet owners, each of whom submits the same transaction.
+#[derive(Serialize, Deserialize, Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::AddOwner")]
+pub struct AddOwner {
+    /// Name of the wallet.
+    pub wallet: String,
+    /// Key of the new owner.
+    pub key: PublicKey,
+    /// Auxiliary number to guarantee [non-idempotence][idempotence] of transactions.
+    ///
+    /// [idempotence]: https://en.wikipedia.org/wiki/Idempotence
+    pub seed: u64,
+    /// Weight of the new owner, 0 for the default weight of 1.
+    pub weight: u32,
+}
+
+/// Remove `key` from the owners of the `wallet`.
+///
+/// Requires the quorum of the wallet owners, each of whom submits the same transaction.
+#[derive(Serialize, Deserialize, Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::RemoveOwner")]
+pub struct RemoveOwner {
+    /// Name of the wallet.
+    pub wallet: String,
+    /// Key of the removed owner.
+    pub key: PublicKey,
+    /// Auxiliary number to guarantee [non-idempotence][idempotence] of transactions.
+    ///
+    /// [idempotence]: https://en.wikipedia.org/wiki/Idempotence
+    pub seed: u64,
+}
+
+/// Replace `old_key` with `new_key` in the owners of the `wallet`.
+///
+/// Requires the quorum of the wallet owners, each of whom submits the same transaction.
+#[derive(Serialize, Deserialize, Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::ReplaceOwner")]
+pub struct ReplaceOwner {
+    /// Name of the wallet.
+    pub wallet: String,
+    /// Key of the replaced owner.
+    pub old_key: PublicKey,
+    /// Key of the new owner.
+    pub new_key: PublicKey,
+    /// Auxiliary number to guarantee [non-idempotence][idempotence] of transactions.
+    ///
+    /// [idempotence]: https://en.wikipedia.org/wiki/Idempotence
+    pub seed: u64,
+}
+
+/// Change the quorum of the `wallet`.
+///
+/// Requires the current quorum of the wallet owners, each of whom submits the same transaction.
+#[derive(Serialize, Deserialize, Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::ChangeQuorum")]
+pub struct ChangeQuorum {
+    /// Name of the wallet.
+    pub wallet: String,
+    /// New quorum size.
+    pub quorum: u32,
+    /// Auxiliary number to guarantee [non-idempotence][idempotence] of transactions.
+    ///
+    /// [idempotence]: https://en.wikipedia.org/wiki/Idempotence
+    pub seed: u64,
+}
+
+/// Change the quorums required for smaller transfers from the `wallet`.
+///
+/// Requires the quorum of the wallet owners, each of whom submits the same transaction.
+#[derive(Serialize, Deserialize, Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::ChangeQuorumTiers")]
+pub struct ChangeQuorumTiers {
+    /// Name of the wallet.
+    pub wallet: String,
+    /// New quorum tiers, ordered by `max_amount`.
+    pub quorum_tiers: Vec<QuorumTier>,
+    /// Auxiliary number to guarantee [non-idempotence][idempotence] of transactions.
+    ///
+    /// [idempotence]: https://en.wikipedia.org/wiki/Idempotence
+    pub seed: u64,
+}
+
+/// Set the amount of currency which can be sent from the `wallet` during `window` blocks
+/// by the lower `quorum` of the owners.
+///
+/// Requires the quorum of the wallet owners, each of whom submits the same transaction.
+#[derive(Serialize, Deserialize, Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::SetSpendingLimit")]
+pub struct SetSpendingLimit {
+    /// Name of the wallet.
+    pub wallet: String,
+    /// Amount of currency which can be sent during the window.
+    pub limit: u64,
+    /// Length of the window in blocks, 0 to remove the limit.
+    pub window: u64,
+    /// Quorum size for transfers within the limit.
     pub quorum: u32,
+    /// Auxiliary number to guarantee [non-idempotence][idempotence] of transactions.
+    ///
+    /// [idempotence]: https://en.wikipedia.org/wiki/Idempotence
+    pub seed: u64,
+}
+
+/// Lock `amount` of the currency from the `from` wallet until the preimage of the
+/// `hashlock` is revealed or the lock times out.
+///
+/// Requires the quorum of the sender's wallet owners, each of whom submits the same
+/// transaction. The hash of the transaction content is the id of the created lock.
+#[derive(Serialize, Deserialize, Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::HtlcLock")]
+pub struct HtlcLock {
+    /// Name of sender's wallet.
+    pub from: String,
+    /// Name of receiver's wallet.
+    pub to: String,
+    /// Amount of currency to lock.
+    pub amount: u64,
+    /// SHA-256 hash of the preimage which unlocks the funds.
+    pub hashlock: Hash,
+    /// Last height at which the funds can be claimed.
+    pub timeout_height: Height,
+    /// Auxiliary number to guarantee [non-idempotence][idempotence] of transactions.
+    ///
+    /// [idempotence]: https://en.wikipedia.org/wiki/Idempotence
+    pub seed: u64,
+}
+
+/// Claim the locked funds for the receiver with the preimage of the hashlock.
+#[derive(Serialize, Deserialize, Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::HtlcClaim")]
+pub struct HtlcClaim {
+    /// Id of the lock.
+    pub id: Hash,
+    /// Preimage of the hashlock.
+    pub preimage: Vec<u8>,
+}
+
+/// Refund the locked funds to the sender once the lock has timed out.
+#[derive(Serialize, Deserialize, Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::HtlcRefund")]
+pub struct HtlcRefund {
+    /// Id of the lock.
+    pub id: Hash,
+}
+
+/// Single output of the batch transfer.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, ProtobufConvert)]
+#[exonum(pb = "proto::TransferOutput")]
+pub struct TransferOutput {
+    /// Name of receiver's wallet.
+    pub to: String,
+    /// Amount of currency to transfer.
+    pub amount: u64,
+}
+
+impl TransferOutput {
+    /// Create new transfer output.
+    pub fn new(to: &str, amount: u64) -> Self {
+        Self {
+            to: to.to_owned(),
+            amount,
+        }
+    }
+}
+
+/// Transfer currency from the `from` wallet to several receivers at once.
+///
+/// Requires the quorum of the sender's wallet owners for the total amount, or the lower
+/// quorum of its spending limit if the total fits into the limit. Each of the owners
+/// submits the same transaction. The transfer is executed atomically: it fails as a whole if any of the
+/// receivers doesn't exist or the total amount exceeds the balance of the sender.
+#[derive(Serialize, Deserialize, Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::BatchTransfer")]
+pub struct BatchTransfer {
+    /// Name of sender's wallet.
+    pub from: String,
+    /// Receivers and amounts of the transfer.
+    pub outputs: Vec<TransferOutput>,
+    /// Auxiliary number to guarantee [non-idempotence][idempotence] of transactions.
+    ///
+    /// [idempotence]: https://en.wikipedia.org/wiki/Idempotence
+    pub seed: u64,
+}
+
+/// Allow the `spender` wallet to transfer up to `amount` of the currency from the `owner`
+/// wallet, replacing the previous allowance. Zero amount revokes the allowance.
+///
+/// Requires the quorum of the owner's wallet owners, each of whom submits the same
+/// transaction.
+#[derive(Serialize, Deserialize, Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::Approve")]
+pub struct Approve {
+    /// Name of the owner's wallet.
+    pub owner: String,
+    /// Name of the spender's wallet.
+    pub spender: String,
+    /// Allowed amount of currency.
+    pub amount: u64,
+    /// Auxiliary number to guarantee [non-idempotence][idempotence] of transactions.
+    ///
+    /// [idempotence]: https://en.wikipedia.org/wiki/Idempotence
+    pub seed: u64,
+}
+
+/// Transfer `amount` of the currency from the `owner` wallet within the allowance of the
+/// `spender` wallet.
+///
+/// Requires the quorum of the spender's wallet owners, each of whom submits the same
+/// transaction.
+#[derive(Serialize, Deserialize, Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::TransferFrom")]
+pub struct TransferFrom {
+    /// Name of the owner's wallet.
+    pub owner: String,
+    /// Name of the spender's wallet.
+    pub spender: String,
+    /// Name of receiver's wallet.
+    pub to: String,
+    /// Amount of currency to transfer.
+    pub amount: u64,
+    /// Auxiliary number to guarantee [non-idempotence][idempotence] of transactions.
+    ///
+    /// [idempotence]: https://en.wikipedia.org/wiki/Idempotence
+    pub seed: u64,
+}
+
+/// Create a subscription paying `amount` of the currency from the `from` wallet to the `to`
+/// wallet `count` times. The service makes the payments every `period_blocks`, starting
+/// `period_blocks` after the subscription is created.
+///
+/// Requires the quorum of the payer's wallet owners, each of whom submits the same
+/// transaction. The hash of the transaction content is the id of the created subscription.
+#[derive(Serialize, Deserialize, Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::CreateSubscription")]
+pub struct CreateSubscription {
+    /// Name of payer's wallet.
+    pub from: String,
+    /// Name of payee's wallet.
+    pub to: String,
+    /// Amount of currency paid every period.
+    pub amount: u64,
+    /// Length of the period in blocks.
+    pub period_blocks: u64,
+    /// Number of payments.
+    pub count: u64,
+    /// Auxiliary number to guarantee [non-idempotence][idempotence] of transactions.
+    ///
+    /// [idempotence]: https://en.wikipedia.org/wiki/Idempotence
+    pub seed: u64,
+}
+
+/// Cancel the subscription with the given id.
+///
+/// Takes effect immediately if submitted by an owner of the payee's wallet, otherwise
+/// requires the quorum of the payer's wallet owners, each of whom submits the same
+/// transaction.
+#[derive(Serialize, Deserialize, Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::CancelSubscription")]
+pub struct CancelSubscription {
+    /// Id of the subscription.
+    pub id: Hash,
+}
+
+/// Request a payment of `amount` of the currency to the `payee` wallet.
+///
+/// Can be submitted by any owner of the payee's wallet. The hash of the transaction is
+/// the id of the created invoice.
+#[derive(Serialize, Deserialize, Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::CreateInvoice")]
+pub struct CreateInvoice {
+    /// Name of payee's wallet.
+    pub payee: String,
+    /// Requested amount of currency.
+    pub amount: u64,
+    /// Reference of the payment, such as an invoice number.
+    pub memo: String,
+    /// Last height at which the invoice can be paid, `Height(0)` if the invoice doesn't expire.
+    pub expires_at: Height,
+    /// Auxiliary number to guarantee [non-idempotence][idempotence] of transactions.
+    ///
+    /// [idempotence]: https://en.wikipedia.org/wiki/Idempotence
+    pub seed: u64,
+}
+
+/// Propose the transfer paying the invoice with the given id from the `from` wallet.
+///
+/// The proposal is filled from the invoice and approved with `ApproveTransfer` like any
+/// other transfer proposal. The hash of the transaction is the id of the proposal.
+#[derive(Serialize, Deserialize, Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::PayInvoice")]
+pub struct PayInvoice {
+    /// Id of the invoice.
+    pub invoice_id: Hash,
+    /// Name of payer's wallet.
+    pub from: String,
+}
+
+/// Create the asset with the given `name` issued by the owners of the `issuer` wallet.
+///
+/// Requires the quorum of the issuer's wallet owners, each of whom submits the same
+/// transaction.
+#[derive(Serialize, Deserialize, Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::CreateAsset")]
+pub struct CreateAsset {
+    /// Unique name of the asset.
+    pub name: String,
+    /// Name of the wallet whose owners may issue the asset.
+    pub issuer: String,
 }
 
 /// Transaction group.
@@ -131,16 +752,75 @@ pub enum WalletTransactions {
     Issue(Issue),
     /// CreateWallet tx.
     CreateWallet(CreateWallet),
+    /// ProposeTransfer tx.
+    ProposeTransfer(ProposeTransfer),
+    /// ApproveTransfer tx.
+    ApproveTransfer(ApproveTransfer),
+    /// RejectTransfer tx.
+    RejectTransfer(RejectTransfer),
+    /// Burn tx.
+    Burn(Burn),
+    /// AddOwner tx.
+    AddOwner(AddOwner),
+    /// RemoveOwner tx.
+    RemoveOwner(RemoveOwner),
+    /// ReplaceOwner tx.
+    ReplaceOwner(ReplaceOwner),
+    /// ChangeQuorum tx.
+    ChangeQuorum(ChangeQuorum),
+    /// ChangeQuorumTiers tx.
+    ChangeQuorumTiers(ChangeQuorumTiers),
+    /// SetSpendingLimit tx.
+    SetSpendingLimit(SetSpendingLimit),
+    /// CancelTransfer tx.
+    CancelTransfer(CancelTransfer),
+    /// HtlcLock tx.
+    HtlcLock(HtlcLock),
+    /// HtlcClaim tx.
+    HtlcClaim(HtlcClaim),
+    /// HtlcRefund tx.
+    HtlcRefund(HtlcRefund),
+    /// BatchTransfer tx.
+    BatchTransfer(BatchTransfer),
+    /// Approve tx.
+    Approve(Approve),
+    /// TransferFrom tx.
+    TransferFrom(TransferFrom),
+    /// CreateSubscription tx.
+    CreateSubscription(CreateSubscription),
+    /// CancelSubscription tx.
+    CancelSubscription(CancelSubscription),
+    /// CreateInvoice tx.
+    CreateInvoice(CreateInvoice),
+    /// PayInvoice tx.
+    PayInvoice(PayInvoice),
+    /// CreateAsset tx.
+    CreateAsset(CreateAsset),
 }
 
 impl CreateWallet {
     #[doc(hidden)]
     pub fn sign(name: &str, pub_keys: Vec<PublicKey>, quorum: u32, pk: &PublicKey, sk: &SecretKey) -> Signed<RawTransaction> {
+        Self::sign_weighted(name, pub_keys, Vec::new(), quorum, Vec::new(), pk, sk)
+    }
+
+    #[doc(hidden)]
+    pub fn sign_weighted(
+        name: &str,
+        pub_keys: Vec<PublicKey>,
+        weights: Vec<u32>,
+        quorum: u32,
+        quorum_tiers: Vec<QuorumTier>,
+        pk: &PublicKey,
+        sk: &SecretKey,
+    ) -> Signed<RawTransaction> {
         Message::sign_transaction(
             Self {
                 name: name.to_owned(),
                 pub_keys,
-                quorum
+                quorum,
+                weights,
+                quorum_tiers,
             },
             CRYPTOCURRENCY_SERVICE_ID,
             *pk,
@@ -159,12 +839,22 @@ impl Issue {
         sk: &SecretKey,
     ) -> Signed<RawTransaction> {
         Message::sign_transaction(
-            Self { to, amount, seed },
+            Self {
+                to,
+                amount,
+                seed,
+                asset: String::new(),
+            },
             CRYPTOCURRENCY_SERVICE_ID,
             *pk,
             sk,
         )
     }
+
+    #[doc(hidden)]
+    pub fn into_signed(self, pk: &PublicKey, sk: &SecretKey) -> Signed<RawTransaction> {
+        Message::sign_transaction(self, CRYPTOCURRENCY_SERVICE_ID, *pk, sk)
+    }
 }
 
 impl Transfer {
@@ -177,80 +867,1403 @@ impl Transfer {
         pk: &PublicKey,
         sk: &SecretKey,
     ) -> Signed<RawTransaction> {
-        Message::sign_transaction(
-            Self { from, to, amount, seed },
-            CRYPTOCURRENCY_SERVICE_ID,
-            *pk,
-            sk,
-        )
+        Self::sign_with_memo(from, to, amount, seed, String::new(), pk, sk)
     }
-}
-
-impl Transaction for Transfer {
-    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
-        let pub_key = &context.author();
-        let from = &self.from;
-        let hash = context.tx_hash();
 
-        let mut schema = Schema::new(context.fork());
+    #[doc(hidden)]
+    pub fn sign_with_memo(
+        from: String,
+        to: String,
+        amount: u64,
+        seed: u64,
+        memo: String,
+        pk: &PublicKey,
+        sk: &SecretKey,
+    ) -> Signed<RawTransaction> {
+        Self {
+            from,
+            to,
+            amount,
+            seed,
+            memo,
+            asset: String::new(),
+        }
+        .into_signed(pk, sk)
+    }
+
+    #[doc(hidden)]
+    pub fn into_signed(self, pk: &PublicKey, sk: &SecretKey) -> Signed<RawTransaction> {
+        Message::sign_transaction(self, CRYPTOCURRENCY_SERVICE_ID, *pk, sk)
+    }
+}
+
+impl ProposeTransfer {
+    #[doc(hidden)]
+    pub fn sign(
+        from: String,
+        to: String,
+        amount: u64,
+        seed: u64,
+        valid_until: Height,
+        pk: &PublicKey,
+        sk: &SecretKey,
+    ) -> Signed<RawTransaction> {
+        Self {
+            from,
+            to,
+            amount,
+            seed,
+            valid_until,
+            execute_at: Height(0),
+            memo: String::new(),
+            asset: String::new(),
+        }
+        .into_signed(pk, sk)
+    }
+
+    #[doc(hidden)]
+    pub fn into_signed(self, pk: &PublicKey, sk: &SecretKey) -> Signed<RawTransaction> {
+        Message::sign_transaction(self, CRYPTOCURRENCY_SERVICE_ID, *pk, sk)
+    }
+}
+
+impl ApproveTransfer {
+    #[doc(hidden)]
+    pub fn sign(proposal_id: &Hash, pk: &PublicKey, sk: &SecretKey) -> Signed<RawTransaction> {
+        Message::sign_transaction(
+            Self { proposal_id: *proposal_id },
+            CRYPTOCURRENCY_SERVICE_ID,
+            *pk,
+            sk,
+        )
+    }
+}
+
+impl RejectTransfer {
+    #[doc(hidden)]
+    pub fn sign(proposal_id: &Hash, pk: &PublicKey, sk: &SecretKey) -> Signed<RawTransaction> {
+        Message::sign_transaction(
+            Self { proposal_id: *proposal_id },
+            CRYPTOCURRENCY_SERVICE_ID,
+            *pk,
+            sk,
+        )
+    }
+}
+
+impl CancelTransfer {
+    #[doc(hidden)]
+    pub fn sign(proposal_id: &Hash, pk: &PublicKey, sk: &SecretKey) -> Signed<RawTransaction> {
+        Message::sign_transaction(
+            Self { proposal_id: *proposal_id },
+            CRYPTOCURRENCY_SERVICE_ID,
+            *pk,
+            sk,
+        )
+    }
+}
+
+impl Burn {
+    #[doc(hidden)]
+    pub fn sign(
+        wallet: String,
+        amount: u64,
+        seed: u64,
+        pk: &PublicKey,
+        sk: &SecretKey,
+    ) -> Signed<RawTransaction> {
+        Message::sign_transaction(
+            Self {
+                wallet,
+                amount,
+                seed,
+            },
+            CRYPTOCURRENCY_SERVICE_ID,
+            *pk,
+            sk,
+        )
+    }
+}
+
+impl AddOwner {
+    #[doc(hidden)]
+    pub fn sign(
+        wallet: String,
+        key: &PublicKey,
+        weight: u32,
+        seed: u64,
+        pk: &PublicKey,
+        sk: &SecretKey,
+    ) -> Signed<RawTransaction> {
+        Message::sign_transaction(
+            Self {
+                wallet,
+                key: *key,
+                seed,
+                weight,
+            },
+            CRYPTOCURRENCY_SERVICE_ID,
+            *pk,
+            sk,
+        )
+    }
+}
+
+impl RemoveOwner {
+    #[doc(hidden)]
+    pub fn sign(
+        wallet: String,
+        key: &PublicKey,
+        seed: u64,
+        pk: &PublicKey,
+        sk: &SecretKey,
+    ) -> Signed<RawTransaction> {
+        Message::sign_transaction(
+            Self {
+                wallet,
+                key: *key,
+                seed,
+            },
+            CRYPTOCURRENCY_SERVICE_ID,
+            *pk,
+            sk,
+        )
+    }
+}
+
+impl ReplaceOwner {
+    #[doc(hidden)]
+    pub fn sign(
+        wallet: String,
+        old_key: &PublicKey,
+        new_key: &PublicKey,
+        seed: u64,
+        pk: &PublicKey,
+        sk: &SecretKey,
+    ) -> Signed<RawTransaction> {
+        Message::sign_transaction(
+            Self {
+                wallet,
+                old_key: *old_key,
+                new_key: *new_key,
+                seed,
+            },
+            CRYPTOCURRENCY_SERVICE_ID,
+            *pk,
+            sk,
+        )
+    }
+}
+
+impl ChangeQuorum {
+    #[doc(hidden)]
+    pub fn sign(
+        wallet: String,
+        quorum: u32,
+        seed: u64,
+        pk: &PublicKey,
+        sk: &SecretKey,
+    ) -> Signed<RawTransaction> {
+        Message::sign_transaction(
+            Self {
+                wallet,
+                quorum,
+                seed,
+            },
+            CRYPTOCURRENCY_SERVICE_ID,
+            *pk,
+            sk,
+        )
+    }
+}
+
+impl ChangeQuorumTiers {
+    #[doc(hidden)]
+    pub fn sign(
+        wallet: String,
+        quorum_tiers: Vec<QuorumTier>,
+        seed: u64,
+        pk: &PublicKey,
+        sk: &SecretKey,
+    ) -> Signed<RawTransaction> {
+        Message::sign_transaction(
+            Self {
+                wallet,
+                quorum_tiers,
+                seed,
+            },
+            CRYPTOCURRENCY_SERVICE_ID,
+            *pk,
+            sk,
+        )
+    }
+}
+
+impl SetSpendingLimit {
+    #[doc(hidden)]
+    pub fn sign(
+        wallet: String,
+        limit: u64,
+        window: u64,
+        quorum: u32,
+        seed: u64,
+        pk: &PublicKey,
+        sk: &SecretKey,
+    ) -> Signed<RawTransaction> {
+        Message::sign_transaction(
+            Self {
+                wallet,
+                limit,
+                window,
+                quorum,
+                seed,
+            },
+            CRYPTOCURRENCY_SERVICE_ID,
+            *pk,
+            sk,
+        )
+    }
+}
+
+impl HtlcLock {
+    #[doc(hidden)]
+    pub fn into_signed(self, pk: &PublicKey, sk: &SecretKey) -> Signed<RawTransaction> {
+        Message::sign_transaction(self, CRYPTOCURRENCY_SERVICE_ID, *pk, sk)
+    }
+}
+
+impl HtlcClaim {
+    #[doc(hidden)]
+    pub fn sign(id: &Hash, preimage: &[u8], pk: &PublicKey, sk: &SecretKey) -> Signed<RawTransaction> {
+        Message::sign_transaction(
+            Self {
+                id: *id,
+                preimage: preimage.to_vec(),
+            },
+            CRYPTOCURRENCY_SERVICE_ID,
+            *pk,
+            sk,
+        )
+    }
+}
+
+impl HtlcRefund {
+    #[doc(hidden)]
+    pub fn sign(id: &Hash, pk: &PublicKey, sk: &SecretKey) -> Signed<RawTransaction> {
+        Message::sign_transaction(Self { id: *id }, CRYPTOCURRENCY_SERVICE_ID, *pk, sk)
+    }
+}
+
+impl BatchTransfer {
+    #[doc(hidden)]
+    pub fn sign(
+        from: String,
+        outputs: Vec<TransferOutput>,
+        seed: u64,
+        pk: &PublicKey,
+        sk: &SecretKey,
+    ) -> Signed<RawTransaction> {
+        Message::sign_transaction(
+            Self {
+                from,
+                outputs,
+                seed,
+            },
+            CRYPTOCURRENCY_SERVICE_ID,
+            *pk,
+            sk,
+        )
+    }
+}
+
+impl Approve {
+    #[doc(hidden)]
+    pub fn sign(
+        owner: String,
+        spender: String,
+        amount: u64,
+        seed: u64,
+        pk: &PublicKey,
+        sk: &SecretKey,
+    ) -> Signed<RawTransaction> {
+        Message::sign_transaction(
+            Self {
+                owner,
+                spender,
+                amount,
+                seed,
+            },
+            CRYPTOCURRENCY_SERVICE_ID,
+            *pk,
+            sk,
+        )
+    }
+}
+
+impl TransferFrom {
+    #[doc(hidden)]
+    pub fn sign(
+        owner: String,
+        spender: String,
+        to: String,
+        amount: u64,
+        seed: u64,
+        pk: &PublicKey,
+        sk: &SecretKey,
+    ) -> Signed<RawTransaction> {
+        Message::sign_transaction(
+            Self {
+                owner,
+                spender,
+                to,
+                amount,
+                seed,
+            },
+            CRYPTOCURRENCY_SERVICE_ID,
+            *pk,
+            sk,
+        )
+    }
+}
+
+impl CreateSubscription {
+    #[doc(hidden)]
+    pub fn into_signed(self, pk: &PublicKey, sk: &SecretKey) -> Signed<RawTransaction> {
+        Message::sign_transaction(self, CRYPTOCURRENCY_SERVICE_ID, *pk, sk)
+    }
+}
+
+impl CancelSubscription {
+    #[doc(hidden)]
+    pub fn sign(id: &Hash, pk: &PublicKey, sk: &SecretKey) -> Signed<RawTransaction> {
+        Message::sign_transaction(Self { id: *id }, CRYPTOCURRENCY_SERVICE_ID, *pk, sk)
+    }
+}
+
+impl CreateInvoice {
+    #[doc(hidden)]
+    pub fn sign(
+        payee: String,
+        amount: u64,
+        memo: String,
+        expires_at: Height,
+        seed: u64,
+        pk: &PublicKey,
+        sk: &SecretKey,
+    ) -> Signed<RawTransaction> {
+        Message::sign_transaction(
+            Self {
+                payee,
+                amount,
+                memo,
+                expires_at,
+                seed,
+            },
+            CRYPTOCURRENCY_SERVICE_ID,
+            *pk,
+            sk,
+        )
+    }
+}
+
+impl PayInvoice {
+    #[doc(hidden)]
+    pub fn sign(invoice_id: &Hash, from: String, pk: &PublicKey, sk: &SecretKey) -> Signed<RawTransaction> {
+        Message::sign_transaction(
+            Self {
+                invoice_id: *invoice_id,
+                from,
+            },
+            CRYPTOCURRENCY_SERVICE_ID,
+            *pk,
+            sk,
+        )
+    }
+}
+
+impl CreateAsset {
+    #[doc(hidden)]
+    pub fn sign(name: String, issuer: String, pk: &PublicKey, sk: &SecretKey) -> Signed<RawTransaction> {
+        Message::sign_transaction(Self { name, issuer }, CRYPTOCURRENCY_SERVICE_ID, *pk, sk)
+    }
+}
+
+/// Returns the id under which approvals of the given wallet operation are collected.
+fn operation_id<T: BinaryForm>(name: &str, operation: &T) -> Hash {
+    HashStream::new()
+        .update(name.as_bytes())
+        .update(&operation.encode().unwrap())
+        .hash()
+}
+
+/// Records the approval of the wallet operation by the given key and returns `true`
+/// once the operation has collected the quorum of the wallet owners.
+///
+/// Operations which don't collect the quorum within the proposal lifetime are dropped.
+fn approve_operation(
+    schema: &mut Schema<&mut Fork>,
+    wallet: &Wallet,
+    id: &Hash,
+    key: &PublicKey,
+) -> Result<bool, ExecutionError> {
+    approve_operation_with_quorum(schema, wallet, id, key, wallet.quorum)
+}
+
+/// Records the approval of the wallet operation by the given key and returns `true`
+/// once the operation has collected the given quorum.
+fn approve_operation_with_quorum(
+    schema: &mut Schema<&mut Fork>,
+    wallet: &Wallet,
+    id: &Hash,
+    key: &PublicKey,
+    quorum: u32,
+) -> Result<bool, ExecutionError> {
+    if !wallet.is_owner(key) {
+        return Err(ExecutionError::new(ERROR_SENDER_WRONG_KEY));
+    }
+
+    let (operation, is_new) = match schema.pending_operation(id) {
+        Some(operation) => (operation, false),
+        None => {
+            let fork: &Fork = schema.as_mut();
+            let height = current_height(fork);
+            let valid_until = ServiceConfig::actual(fork).proposal_deadline(height);
+            (PendingOperation::new(&wallet.name, Vec::new(), valid_until), true)
+        }
+    };
+    if operation.is_approved_by(key) {
+        return Err(Error::AlreadyApproved.into());
+    }
+
+    let operation = operation.add_approval(key);
+    if wallet.count_approvals(&operation.approvals) < quorum {
+        if is_new {
+            schema.create_pending_operation(id, operation);
+        } else {
+            schema.put_pending_operation(id, operation);
+        }
+        Ok(false)
+    } else {
+        schema.remove_pending_operation(id);
+        Ok(true)
+    }
+}
+
+/// Checks that the wallet keys are distinct, that the weights match the keys and
+/// that the quorums are reachable by the owners.
+pub(crate) fn check_owners(
+    pub_keys: &[PublicKey],
+    weights: &[u32],
+    quorum: u32,
+    quorum_tiers: &[QuorumTier],
+) -> Result<(), Error> {
+    if pub_keys.iter().enumerate().any(|(i, key)| pub_keys[..i].contains(key)) {
+        return Err(Error::OwnerAlreadyExists);
+    }
+
+    if !weights.is_empty() && (weights.len() != pub_keys.len() || weights.contains(&0)) {
+        return Err(Error::WeightsNotCorrect);
+    }
+
+    let total_weight = if weights.is_empty() {
+        pub_keys.len() as u64
+    } else {
+        weights.iter().map(|&weight| u64::from(weight)).sum()
+    };
+    // Approvals are counted in `u32`, so the total weight must fit into it.
+    if total_weight > u64::from(u32::max_value()) {
+        return Err(Error::WeightsNotCorrect);
+    }
+    let is_reachable = |quorum: u32| quorum > 0 && u64::from(quorum) <= total_weight;
+    if !is_reachable(quorum) || !quorum_tiers.iter().all(|tier| is_reachable(tier.quorum)) {
+        return Err(Error::QuorumIsNotCorrect);
+    }
+
+    if quorum_tiers.windows(2).any(|tiers| tiers[0].max_amount >= tiers[1].max_amount) {
+        return Err(Error::QuorumTiersNotCorrect);
+    }
+    Ok(())
+}
+
+/// Returns the hash time-locked contract with the given id if its funds are still locked.
+fn locked_htlc(schema: &Schema<&mut Fork>, id: &Hash) -> Result<Htlc, Error> {
+    let htlc = schema.htlc(id).ok_or(Error::HtlcNotFound)?;
+    if htlc.status != HtlcStatus::Locked {
+        return Err(Error::HtlcNotLocked);
+    }
+    Ok(htlc)
+}
+
+/// Returns the invoice with the given id if it can be paid at the given height.
+fn open_invoice(schema: &Schema<&mut Fork>, id: &Hash, height: Height) -> Result<Invoice, Error> {
+    let invoice = schema.invoice(id).ok_or(Error::InvoiceNotFound)?;
+    if invoice.status != InvoiceStatus::Open {
+        return Err(Error::InvoiceAlreadyPaid);
+    }
+    if invoice.is_expired_at(height) {
+        return Err(Error::InvoiceExpired);
+    }
+    Ok(invoice)
+}
+
+/// Moves the fee from the payer's wallet to the treasury wallet. No fees are charged
+/// if the treasury wallet is not configured.
+fn charge_fee(
+    schema: &mut Schema<&mut Fork>,
+    config: &ServiceConfig,
+    payer: &str,
+    fee: u64,
+    transaction: &Hash,
+) -> Result<(), Error> {
+    let treasury = match config.treasury {
+        Some(ref treasury) if fee > 0 && treasury != payer => treasury,
+        _ => return Ok(()),
+    };
+    let treasury = schema
+        .wallet(&crypto::hash(treasury.as_bytes()))
+        .ok_or(Error::TreasuryNotFound)?;
+    let payer = schema.wallet(&crypto::hash(payer.as_bytes())).ok_or(Error::SenderNotFound)?;
+    if payer.balance < fee {
+        return Err(Error::InsufficientFee);
+    }
+    schema.pay_fee(payer, treasury, fee, transaction);
+    Ok(())
+}
+
+/// Returns the height of the block being created.
+fn current_height(fork: &Fork) -> Height {
+    blockchain::Schema::new(fork).height().next()
+}
+
+/// Returns the transfer proposal with the given id if it can be approved at the given height.
+fn pending_proposal(
+    schema: &Schema<&mut Fork>,
+    id: &Hash,
+    height: Height,
+) -> Result<TransferProposal, Error> {
+    if let Some(proposal) = schema.transfer_proposal(id) {
+        if proposal.status != ProposalStatus::Pending {
+            Err(Error::ProposalNotPending)
+        } else if proposal.is_expired_at(height) {
+            Err(Error::ProposalExpired)
+        } else {
+            Ok(proposal)
+        }
+    } else if let Some(proposal) = schema.archived_transfer_proposal(id) {
+        if proposal.status == ProposalStatus::Expired {
+            Err(Error::ProposalExpired)
+        } else {
+            Err(Error::ProposalNotPending)
+        }
+    } else {
+        Err(Error::ProposalNotFound)
+    }
+}
+
+/// Records the approval of the transfer proposal by the given key.
+fn approve_proposal(
+    schema: &mut Schema<&mut Fork>,
+    id: &Hash,
+    proposal: TransferProposal,
+    key: &PublicKey,
+) -> ExecutionResult {
+    if proposal.is_approved_by(key) {
+        return Err(Error::AlreadyApproved.into());
+    }
+    if proposal.is_rejected_by(key) {
+        return Err(Error::AlreadyRejected.into());
+    }
+    schema.approve_transfer_proposal(id, proposal, key);
+    Ok(())
+}
+
+/// Returns `true` if the approvals of the transfer proposal have reached the lower quorum
+/// of the spending limit of the sender's wallet.
+fn reaches_limit_quorum(schema: &Schema<&mut Fork>, proposal: &TransferProposal, sender: &Wallet) -> bool {
+    proposal.asset == NATIVE_ASSET
+        && schema
+            .spending_limit(&crypto::hash(proposal.from.as_bytes()))
+            .map_or(false, |limit| sender.count_approvals(&proposal.approvals) >= limit.quorum)
+}
+
+/// Checks the pending transfer proposal which hasn't been executed. If it has collected
+/// the lower quorum of the spending limit, it doesn't fit into the limit, so it fails
+/// with `LimitExceeded` once the full quorum can no longer be reached. Otherwise the
+/// approvals are kept until the full quorum is collected.
+fn check_spending_limit(schema: &Schema<&mut Fork>, id: &Hash, sender: &Wallet) -> Result<(), Error> {
+    let proposal = schema.transfer_proposal(id).ok_or(Error::ProposalNotFound)?;
+    if reaches_limit_quorum(schema, &proposal, sender)
+        && sender.is_quorum_unreachable(&proposal.rejections, proposal_quorum(&proposal, sender))
+    {
+        return Err(Error::LimitExceeded);
+    }
+    Ok(())
+}
+
+/// Returns the quorum of the sender's wallet required for the transfer proposal. Quorum
+/// tiers only apply to the native currency, since amounts of different assets can't be
+/// compared.
+pub(crate) fn proposal_quorum(proposal: &TransferProposal, sender: &Wallet) -> u32 {
+    if proposal.asset == NATIVE_ASSET {
+        sender.transfer_quorum(proposal.amount)
+    } else {
+        sender.quorum
+    }
+}
+
+/// Returns the quorum required to execute the transfer proposal at the given height:
+/// the lower quorum of the spending limit of the sender's wallet if the transfer fits
+/// into the limit, or the full quorum of the wallet otherwise.
+pub(crate) fn required_quorum<T: AsRef<dyn Snapshot>>(
+    schema: &Schema<T>,
+    proposal: &TransferProposal,
+    sender: &Wallet,
+    height: Height,
+) -> u32 {
+    let quorum = proposal_quorum(proposal, sender);
+    if proposal.asset != NATIVE_ASSET {
+        return quorum;
+    }
+    schema
+        .spending_limit(&crypto::hash(proposal.from.as_bytes()))
+        .filter(|limit| limit.allows(proposal.amount, height))
+        .map_or(quorum, |limit| limit.quorum.min(quorum))
+}
+
+/// Executes the transfer proposal if its approvals have reached the quorum of the
+/// sender's wallet, or the lower quorum of its spending limit and the transfer fits into
+/// the limit. Transfers with a future execution height are only reserved from the sender's
+/// balance. Transfers of the native currency are charged with the fee proportional to
+/// the amount. Returns `true` if the transfer has been executed or scheduled.
+fn execute_proposal(
+    schema: &mut Schema<&mut Fork>,
+    config: &ServiceConfig,
+    id: &Hash,
+    transaction: &Hash,
+    height: Height,
+) -> Result<bool, ExecutionError> {
+    let proposal = schema.transfer_proposal(id).ok_or(Error::ProposalNotFound)?;
+    let sender = schema
+        .wallet(&crypto::hash(proposal.from.as_bytes()))
+        .ok_or(Error::SenderNotFound)?;
+    let receiver = schema
+        .wallet(&crypto::hash(proposal.to.as_bytes()))
+        .ok_or(Error::ReceiverNotFound)?;
+
+    // Only the weights of distinct owners of the wallet count toward the quorum.
+    if sender.count_approvals(&proposal.approvals) < required_quorum(schema, &proposal, &sender, height) {
+        //Err(Error::NotEnoughSignsYet)?
+        return Ok(false);
+    }
+
+    if schema.balance(&sender, &proposal.asset) < proposal.amount {
+        return Err(Error::InsufficientCurrencyAmount.into());
+    }
+
+    // Each invoice is paid once, even if several transfers have been proposed for it,
+    // so the other proposals are cancelled.
+    let invoice = if proposal.invoice_id == Hash::zero() {
+        None
+    } else {
+        Some(open_invoice(schema, &proposal.invoice_id, height)?)
+    };
+    if let Some(invoice) = invoice {
+        schema.pay_invoice(&proposal.invoice_id, invoice, id);
+    }
+
+    let fee = if proposal.asset == NATIVE_ASSET {
+        schema.record_spending(&proposal.from, proposal.amount, height);
+        config.fees.transfer_fee(proposal.amount)
+    } else {
+        0
+    };
+    let from = proposal.from.clone();
+    if proposal.execute_at > height {
+        schema.schedule_transfer_proposal(id, proposal, sender, transaction);
+    } else {
+        schema.decrease_balance(sender, &proposal.asset, proposal.amount, transaction);
+        schema.increase_balance(receiver, &proposal.asset, proposal.amount, transaction);
+        schema.archive_transfer_proposal(id, proposal, ProposalStatus::Executed);
+    }
+    charge_fee(schema, config, &from, fee, transaction)?;
+
+    Ok(true)
+}
+
+impl Transaction for Transfer {
+    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
+        let pub_key = &context.author();
+        let from = &self.from;
+        let hash = context.tx_hash();
+        let height = current_height(context.fork());
+        let config = ServiceConfig::actual(context.fork());
+
+        let mut schema = Schema::new(context.fork());
 
         let to = &self.to;
         let amount = self.amount;
         let seed = self.seed;
 
-        if from == to {
+        if from == to {
+            return Err(ExecutionError::new(ERROR_SENDER_SAME_AS_RECEIVER));
+        }
+
+        if self.memo.len() > MAX_MEMO_LENGTH {
+            return Err(Error::MemoTooLong.into());
+        }
+
+        if self.asset != NATIVE_ASSET && schema.asset(&self.asset).is_none() {
+            return Err(Error::AssetNotFound.into());
+        }
+
+        let sender = schema.wallet(&crypto::hash(from.as_bytes())).ok_or(Error::SenderNotFound)?;
+
+        if !sender.is_owner(pub_key) {
+            return Err(ExecutionError::new(ERROR_SENDER_WRONG_KEY));
+        }
+
+        schema.wallet(&crypto::hash(to.as_bytes())).ok_or(Error::ReceiverNotFound)?;
+
+        charge_fee(&mut schema, &config, from, config.fees.fixed_fee("Transfer"), &hash)?;
+
+        let valid_until = config.proposal_deadline(height);
+        let proposal = TransferProposal::new(
+            from,
+            to,
+            amount,
+            seed,
+            vec![*pub_key],
+            valid_until,
+            Height(0),
+            &self.memo,
+        )
+        .set_asset(&self.asset);
+        let proposal_id = proposal.hash();
+        match pending_proposal(&schema, &proposal_id, height) {
+            Err(Error::ProposalNotFound) => schema.create_transfer_proposal(&proposal_id, proposal),
+            Err(e) => return Err(e.into()),
+            Ok(proposal) => approve_proposal(&mut schema, &proposal_id, proposal, pub_key)?,
+        }
+
+        if !execute_proposal(&mut schema, &config, &proposal_id, &hash, height)? {
+            check_spending_limit(&schema, &proposal_id, &sender)?;
+        }
+        Ok(())
+    }
+}
+
+impl Transaction for ProposeTransfer {
+    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
+        let pub_key = &context.author();
+        let hash = context.tx_hash();
+        let height = current_height(context.fork());
+        let config = ServiceConfig::actual(context.fork());
+
+        let mut schema = Schema::new(context.fork());
+
+        let from = &self.from;
+        let to = &self.to;
+
+        if from == to {
+            return Err(ExecutionError::new(ERROR_SENDER_SAME_AS_RECEIVER));
+        }
+
+        if self.memo.len() > MAX_MEMO_LENGTH {
+            return Err(Error::MemoTooLong.into());
+        }
+
+        if self.asset != NATIVE_ASSET && schema.asset(&self.asset).is_none() {
+            return Err(Error::AssetNotFound.into());
+        }
+
+        let sender = schema.wallet(&crypto::hash(from.as_bytes())).ok_or(Error::SenderNotFound)?;
+
+        if !sender.is_owner(pub_key) {
+            return Err(ExecutionError::new(ERROR_SENDER_WRONG_KEY));
+        }
+
+        schema.wallet(&crypto::hash(to.as_bytes())).ok_or(Error::ReceiverNotFound)?;
+
+        // Proposals can't be approved for longer than the configured lifetime.
+        let max_valid_until = config.proposal_deadline(height);
+        let valid_until = if self.valid_until == Height(0) || self.valid_until > max_valid_until {
+            max_valid_until
+        } else {
+            self.valid_until
+        };
+        if height > valid_until {
+            return Err(Error::ProposalExpired.into());
+        }
+
+        charge_fee(&mut schema, &config, from, config.fees.fixed_fee("ProposeTransfer"), &hash)?;
+
+        let proposal = TransferProposal::new(
+            from,
+            to,
+            self.amount,
+            self.seed,
+            vec![*pub_key],
+            valid_until,
+            self.execute_at,
+            &self.memo,
+        )
+        .set_asset(&self.asset);
+        schema.create_transfer_proposal(&hash, proposal);
+
+        execute_proposal(&mut schema, &config, &hash, &hash, height)?;
+        Ok(())
+    }
+}
+
+impl Transaction for ApproveTransfer {
+    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
+        let pub_key = &context.author();
+        let hash = context.tx_hash();
+        let height = current_height(context.fork());
+        let config = ServiceConfig::actual(context.fork());
+
+        let mut schema = Schema::new(context.fork());
+
+        let id = &self.proposal_id;
+        let proposal = pending_proposal(&schema, id, height)?;
+        let sender = schema
+            .wallet(&crypto::hash(proposal.from.as_bytes()))
+            .ok_or(Error::SenderNotFound)?;
+
+        if !sender.is_owner(pub_key) {
+            return Err(ExecutionError::new(ERROR_SENDER_WRONG_KEY));
+        }
+
+        let fee = config.fees.fixed_fee("ApproveTransfer");
+        charge_fee(&mut schema, &config, &proposal.from, fee, &hash)?;
+        approve_proposal(&mut schema, id, proposal, pub_key)?;
+
+        if !execute_proposal(&mut schema, &config, id, &hash, height)? {
+            check_spending_limit(&schema, id, &sender)?;
+        }
+        Ok(())
+    }
+}
+
+impl Transaction for RejectTransfer {
+    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
+        let pub_key = &context.author();
+        let hash = context.tx_hash();
+        let height = current_height(context.fork());
+        let config = ServiceConfig::actual(context.fork());
+
+        let mut schema = Schema::new(context.fork());
+
+        let id = &self.proposal_id;
+        let proposal = pending_proposal(&schema, id, height)?;
+        let sender = schema
+            .wallet(&crypto::hash(proposal.from.as_bytes()))
+            .ok_or(Error::SenderNotFound)?;
+
+        if !sender.is_owner(pub_key) {
+            return Err(ExecutionError::new(ERROR_SENDER_WRONG_KEY));
+        }
+        if proposal.is_approved_by(pub_key) {
+            return Err(Error::AlreadyApproved.into());
+        }
+        if proposal.is_rejected_by(pub_key) {
+            return Err(Error::AlreadyRejected.into());
+        }
+
+        let fee = config.fees.fixed_fee("RejectTransfer");
+        charge_fee(&mut schema, &config, &proposal.from, fee, &hash)?;
+        schema.reject_transfer_proposal(id, proposal, pub_key);
+
+        // The proposal is rejected as soon as too few owners are left to reach the quorum.
+        let proposal = schema.transfer_proposal(id).unwrap();
+        if sender.is_quorum_unreachable(&proposal.rejections, proposal_quorum(&proposal, &sender)) {
+            schema.archive_transfer_proposal(id, proposal, ProposalStatus::Rejected);
+        }
+
+        Ok(())
+    }
+}
+
+impl Transaction for CancelTransfer {
+    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
+        let pub_key = &context.author();
+        let hash = context.tx_hash();
+        let height = current_height(context.fork());
+
+        let mut schema = Schema::new(context.fork());
+
+        let id = &self.proposal_id;
+        let proposal = match schema.transfer_proposal(id) {
+            Some(ref proposal) if proposal.status == ProposalStatus::Scheduled => proposal.clone(),
+            _ => pending_proposal(&schema, id, height)?,
+        };
+        let sender = schema
+            .wallet(&crypto::hash(proposal.from.as_bytes()))
+            .ok_or(Error::SenderNotFound)?;
+
+        if !approve_operation(&mut schema, &sender, &operation_id("cancel_transfer", self), pub_key)? {
+            return Ok(());
+        }
+
+        if proposal.status == ProposalStatus::Scheduled {
+            schema.cancel_scheduled_transfer_proposal(id, proposal, &hash);
+        } else {
+            schema.append_wallet_history(sender, &hash);
+            schema.archive_transfer_proposal(id, proposal, ProposalStatus::Cancelled);
+        }
+        Ok(())
+    }
+}
+
+impl Transaction for HtlcLock {
+    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
+        let pub_key = &context.author();
+        let hash = context.tx_hash();
+        let height = current_height(context.fork());
+
+        let mut schema = Schema::new(context.fork());
+
+        if self.from == self.to {
+            return Err(ExecutionError::new(ERROR_SENDER_SAME_AS_RECEIVER));
+        }
+
+        let sender = schema
+            .wallet(&crypto::hash(self.from.as_bytes()))
+            .ok_or(Error::SenderNotFound)?;
+        schema.wallet(&crypto::hash(self.to.as_bytes())).ok_or(Error::ReceiverNotFound)?;
+
+        if height > self.timeout_height {
+            return Err(Error::HtlcTimedOut.into());
+        }
+
+        let id = self.hash();
+        if schema.htlc(&id).is_some() {
+            return Err(Error::HtlcAlreadyExists.into());
+        }
+
+        if !approve_operation(&mut schema, &sender, &operation_id("htlc_lock", self), pub_key)? {
+            return Ok(());
+        }
+
+        if sender.balance < self.amount {
+            return Err(Error::InsufficientCurrencyAmount.into());
+        }
+
+        let htlc = Htlc::new(&self.from, &self.to, self.amount, &self.hashlock, self.timeout_height);
+        schema.create_htlc(&id, htlc, sender, &hash);
+        Ok(())
+    }
+}
+
+impl Transaction for HtlcClaim {
+    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
+        let hash = context.tx_hash();
+        let height = current_height(context.fork());
+
+        let mut schema = Schema::new(context.fork());
+
+        let htlc = locked_htlc(&schema, &self.id)?;
+        if htlc.is_timed_out_at(height) {
+            return Err(Error::HtlcTimedOut.into());
+        }
+        if !htlc.is_unlocked_by(&self.preimage) {
+            return Err(Error::InvalidPreimage.into());
+        }
+
+        let receiver = schema
+            .wallet(&crypto::hash(htlc.to.as_bytes()))
+            .ok_or(Error::ReceiverNotFound)?;
+        schema.claim_htlc(&self.id, htlc, &self.preimage, receiver, &hash);
+        Ok(())
+    }
+}
+
+impl Transaction for HtlcRefund {
+    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
+        let hash = context.tx_hash();
+        let height = current_height(context.fork());
+
+        let mut schema = Schema::new(context.fork());
+
+        let htlc = locked_htlc(&schema, &self.id)?;
+        if !htlc.is_timed_out_at(height) {
+            return Err(Error::HtlcNotTimedOut.into());
+        }
+
+        let sender = schema
+            .wallet(&crypto::hash(htlc.from.as_bytes()))
+            .ok_or(Error::SenderNotFound)?;
+        schema.refund_htlc(&self.id, htlc, sender, &hash);
+        Ok(())
+    }
+}
+
+impl Transaction for BatchTransfer {
+    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
+        let pub_key = &context.author();
+        let hash = context.tx_hash();
+        let height = current_height(context.fork());
+        let config = ServiceConfig::actual(context.fork());
+
+        let mut schema = Schema::new(context.fork());
+
+        if self.outputs.is_empty() {
+            return Err(Error::EmptyBatch.into());
+        }
+        if self.outputs.iter().any(|output| output.to == self.from) {
             return Err(ExecutionError::new(ERROR_SENDER_SAME_AS_RECEIVER));
         }
 
-        let sender = schema.wallet(&crypto::hash(from.as_bytes())).ok_or(Error::SenderNotFound)?;
+        let sender = schema
+            .wallet(&crypto::hash(self.from.as_bytes()))
+            .ok_or(Error::SenderNotFound)?;
+        for output in &self.outputs {
+            schema.wallet(&crypto::hash(output.to.as_bytes())).ok_or(Error::ReceiverNotFound)?;
+        }
 
-        let mut ok = false;
-        for key in &sender.pub_keys {
-            if pub_key.to_hex() == key.to_hex() {
-                ok = true;
+        let total = self
+            .outputs
+            .iter()
+            .try_fold(0_u64, |total, output| total.checked_add(output.amount))
+            .ok_or(Error::InsufficientCurrencyAmount)?;
+
+        // The batch needs the quorum for its total amount, or only the lower quorum of
+        // the spending limit if it fits into the limit.
+        let quorum = match schema.spending_limit(&crypto::hash(self.from.as_bytes())) {
+            Some(ref limit) if limit.allows(total, height) => {
+                limit.quorum.min(sender.transfer_quorum(total))
             }
+            _ => sender.transfer_quorum(total),
+        };
+
+        let id = operation_id("batch_transfer", self);
+        if !approve_operation_with_quorum(&mut schema, &sender, &id, pub_key, quorum)? {
+            return Ok(());
         }
 
-        if !ok {
-            return Err(ExecutionError::new(ERROR_SENDER_WRONG_KEY));
+        // The fixed fee is charged once, with the approval which executes the batch.
+        charge_fee(&mut schema, &config, &self.from, config.fees.fixed_fee("BatchTransfer"), &hash)?;
+        // The balance of the sender's wallet may have changed by the fee.
+        let sender = schema.wallet(&crypto::hash(self.from.as_bytes())).unwrap();
+        if total > sender.balance {
+            return Err(Error::InsufficientCurrencyAmount.into());
+        }
+
+        schema.record_spending(&self.from, total, height);
+        schema.decrease_wallet_balance(sender, total, &hash);
+        for output in &self.outputs {
+            // The same wallet may receive several outputs, so it is read anew every time.
+            let receiver = schema.wallet(&crypto::hash(output.to.as_bytes())).unwrap();
+            schema.increase_wallet_balance(receiver, output.amount, &hash);
+        }
+        charge_fee(&mut schema, &config, &self.from, config.fees.transfer_fee(total), &hash)?;
+        Ok(())
+    }
+}
+
+impl Transaction for Approve {
+    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
+        let pub_key = &context.author();
+        let hash = context.tx_hash();
+
+        let mut schema = Schema::new(context.fork());
+
+        if self.owner == self.spender {
+            return Err(ExecutionError::new(ERROR_SENDER_SAME_AS_RECEIVER));
         }
 
-        let receiver = schema.wallet(&crypto::hash(to.as_bytes())).ok_or(Error::ReceiverNotFound)?;
+        let owner = schema
+            .wallet(&crypto::hash(self.owner.as_bytes()))
+            .ok_or(Error::SenderNotFound)?;
+        schema
+            .wallet(&crypto::hash(self.spender.as_bytes()))
+            .ok_or(Error::ReceiverNotFound)?;
 
-        let transfer_proposal = TransferProposal::new(from, to, amount, seed, 1);
-        if schema.transfer_proposal(&transfer_proposal.hash()).is_none() {
-            schema.create_transfer_proposal(from, to, amount, seed);
-        } else {
-            schema.increase_proposal_signs(transfer_proposal);
+        if !approve_operation(&mut schema, &owner, &operation_id("approve", self), pub_key)? {
+            return Ok(());
         }
-        let transfer_proposal = schema.transfer_proposal(
-            &TransferProposal::new(from, to, amount, seed, 1).hash()).unwrap();
 
-        if transfer_proposal.signs != sender.quorum {
-            //Err(Error::NotEnoughSignsYet)?
-            Ok(())
-        } else {
-            if sender.balance < amount {
-                Err(Error::InsufficientCurrencyAmount)?
+        schema.set_allowance(Allowance::new(&self.owner, &self.spender, self.amount));
+        schema.append_wallet_history(owner, &hash);
+        Ok(())
+    }
+}
+
+impl Transaction for TransferFrom {
+    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
+        let pub_key = &context.author();
+        let hash = context.tx_hash();
+        let height = current_height(context.fork());
+
+        let mut schema = Schema::new(context.fork());
+
+        if self.owner == self.to {
+            return Err(ExecutionError::new(ERROR_SENDER_SAME_AS_RECEIVER));
+        }
+
+        let owner = schema
+            .wallet(&crypto::hash(self.owner.as_bytes()))
+            .ok_or(Error::SenderNotFound)?;
+        let spender = schema
+            .wallet(&crypto::hash(self.spender.as_bytes()))
+            .ok_or(Error::SenderNotFound)?;
+        let receiver = schema
+            .wallet(&crypto::hash(self.to.as_bytes()))
+            .ok_or(Error::ReceiverNotFound)?;
+
+        if !approve_operation(&mut schema, &spender, &operation_id("transfer_from", self), pub_key)? {
+            return Ok(());
+        }
+
+        let allowance = schema
+            .allowance(&self.owner, &self.spender)
+            .filter(|allowance| allowance.amount >= self.amount)
+            .ok_or(Error::AllowanceExceeded)?;
+
+        if owner.balance < self.amount {
+            return Err(Error::InsufficientCurrencyAmount.into());
+        }
+        schema.record_spending(&self.owner, self.amount, height);
+        schema.transfer_from(allowance, owner, receiver, self.amount, &hash);
+        Ok(())
+    }
+}
+
+impl Transaction for CreateSubscription {
+    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
+        let pub_key = &context.author();
+        let height = current_height(context.fork());
+
+        let mut schema = Schema::new(context.fork());
+
+        if self.from == self.to {
+            return Err(ExecutionError::new(ERROR_SENDER_SAME_AS_RECEIVER));
+        }
+
+        let sender = schema
+            .wallet(&crypto::hash(self.from.as_bytes()))
+            .ok_or(Error::SenderNotFound)?;
+        schema.wallet(&crypto::hash(self.to.as_bytes())).ok_or(Error::ReceiverNotFound)?;
+
+        let last_payment = self
+            .period_blocks
+            .checked_mul(self.count)
+            .and_then(|duration| height.0.checked_add(duration));
+        if self.period_blocks == 0 || self.count == 0 || last_payment.is_none() {
+            return Err(Error::SubscriptionNotCorrect.into());
+        }
+
+        let id = self.hash();
+        if schema.subscription(&id).is_some() {
+            return Err(Error::SubscriptionAlreadyExists.into());
+        }
+
+        if !approve_operation(&mut schema, &sender, &operation_id("create_subscription", self), pub_key)? {
+            return Ok(());
+        }
+
+        let next_payment = Height(height.0 + self.period_blocks);
+        let subscription = Subscription::new(
+            &self.from,
+            &self.to,
+            self.amount,
+            self.period_blocks,
+            self.count,
+            next_payment,
+        );
+        schema.create_subscription(&id, subscription);
+        Ok(())
+    }
+}
+
+impl Transaction for CancelSubscription {
+    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
+        let pub_key = &context.author();
+
+        let mut schema = Schema::new(context.fork());
+
+        let subscription = schema.subscription(&self.id).ok_or(Error::SubscriptionNotFound)?;
+
+        // Any owner of the payee's wallet may stop the payments.
+        let is_payee = schema
+            .wallet(&crypto::hash(subscription.to.as_bytes()))
+            .map_or(false, |receiver| receiver.is_owner(pub_key));
+        if !is_payee {
+            let sender = schema
+                .wallet(&crypto::hash(subscription.from.as_bytes()))
+                .ok_or(Error::SenderNotFound)?;
+            if !approve_operation(&mut schema, &sender, &operation_id("cancel_subscription", self), pub_key)? {
+                return Ok(());
             }
+        }
 
-            schema.decrease_wallet_balance(sender, amount, &hash);
-            schema.increase_wallet_balance(receiver, amount, &hash);
+        schema.cancel_subscription(&self.id);
+        Ok(())
+    }
+}
 
-            Ok(())
+impl Transaction for CreateInvoice {
+    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
+        let pub_key = &context.author();
+        let hash = context.tx_hash();
+        let height = current_height(context.fork());
+
+        let mut schema = Schema::new(context.fork());
+
+        let payee = schema
+            .wallet(&crypto::hash(self.payee.as_bytes()))
+            .ok_or(Error::ReceiverNotFound)?;
+        if !payee.is_owner(pub_key) {
+            return Err(ExecutionError::new(ERROR_SENDER_WRONG_KEY));
+        }
+
+        if self.memo.len() > MAX_MEMO_LENGTH {
+            return Err(Error::MemoTooLong.into());
+        }
+
+        let invoice = Invoice::new(&self.payee, self.amount, &self.memo, self.expires_at);
+        if invoice.is_expired_at(height) {
+            return Err(Error::InvoiceExpired.into());
+        }
+
+        schema.create_invoice(&hash, invoice);
+        Ok(())
+    }
+}
+
+impl Transaction for PayInvoice {
+    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
+        let pub_key = &context.author();
+        let hash = context.tx_hash();
+        let height = current_height(context.fork());
+        let config = ServiceConfig::actual(context.fork());
+
+        let mut schema = Schema::new(context.fork());
+
+        let invoice = open_invoice(&schema, &self.invoice_id, height)?;
+
+        if self.from == invoice.payee {
+            return Err(ExecutionError::new(ERROR_SENDER_SAME_AS_RECEIVER));
+        }
+
+        let sender = schema
+            .wallet(&crypto::hash(self.from.as_bytes()))
+            .ok_or(Error::SenderNotFound)?;
+
+        if !sender.is_owner(pub_key) {
+            return Err(ExecutionError::new(ERROR_SENDER_WRONG_KEY));
+        }
+
+        schema
+            .wallet(&crypto::hash(invoice.payee.as_bytes()))
+            .ok_or(Error::ReceiverNotFound)?;
+
+        let valid_until = config.proposal_deadline(height);
+        let proposal = TransferProposal::new(
+            &self.from,
+            &invoice.payee,
+            invoice.amount,
+            0,
+            vec![*pub_key],
+            valid_until,
+            Height(0),
+            &invoice.memo,
+        )
+        .set_invoice(&self.invoice_id);
+        schema.create_transfer_proposal(&hash, proposal);
+
+        execute_proposal(&mut schema, &config, &hash, &hash, height)?;
+        Ok(())
+    }
+}
+
+/// Issues the asset other than the native currency, which requires the quorum of the
+/// owners of the issuer's wallet.
+fn issue_asset(issue: &Issue, mut context: TransactionContext) -> ExecutionResult {
+    let pub_key = &context.author();
+    let hash = context.tx_hash();
+
+    let mut schema = Schema::new(context.fork());
+
+    let asset = schema.asset(&issue.asset).ok_or(Error::AssetNotFound)?;
+    let issuer = schema
+        .wallet(&crypto::hash(asset.issuer.as_bytes()))
+        .filter(|issuer| issuer.is_owner(pub_key))
+        .ok_or(Error::UnauthorizedIssuer)?;
+
+    let receiver = schema
+        .wallet(&crypto::hash(issue.to.as_bytes()))
+        .ok_or(Error::ReceiverNotFound)?;
+
+    if !approve_operation(&mut schema, &issuer, &operation_id("issue", issue), pub_key)? {
+        return Ok(());
+    }
+
+    let asset = asset.issue(issue.amount).ok_or(Error::SupplyOverflow)?;
+    schema.issue_asset(asset, receiver, issue.amount, &hash);
+    Ok(())
+}
+
+impl Transaction for CreateAsset {
+    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
+        let pub_key = &context.author();
+        let config = ServiceConfig::actual(context.fork());
+
+        let mut schema = Schema::new(context.fork());
+
+        if self.name.len() > config.max_name_length {
+            return Err(Error::NameTooLong.into());
+        }
+
+        // The native currency is always present under the empty name.
+        if self.name == NATIVE_ASSET || schema.asset(&self.name).is_some() {
+            return Err(Error::AssetAlreadyExists.into());
         }
+
+        let issuer = schema
+            .wallet(&crypto::hash(self.issuer.as_bytes()))
+            .ok_or(Error::SenderNotFound)?;
+
+        if !approve_operation(&mut schema, &issuer, &operation_id("create_asset", self), pub_key)? {
+            return Ok(());
+        }
+
+        schema.create_asset(Asset::new(&self.name, &self.issuer));
+        Ok(())
     }
 }
 
 impl Transaction for Issue {
     fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
-        let _pub_key = &context.author();
+        if self.asset != NATIVE_ASSET {
+            return issue_asset(self, context);
+        }
+
+        let pub_key = &context.author();
         let hash = context.tx_hash();
+        let config = ServiceConfig::actual(context.fork());
+
+        if !config.is_issuer(pub_key) {
+            return Err(Error::UnauthorizedIssuer.into());
+        }
+
+        let max_supply = config.max_supply;
 
         let mut schema = Schema::new(context.fork());
 
+        let supply = schema
+            .total_supply()
+            .get()
+            .unwrap_or_default()
+            .checked_add(self.amount)
+            .ok_or(Error::SupplyOverflow)?;
+        if max_supply.map_or(false, |max_supply| supply > max_supply) {
+            return Err(Error::SupplyCapExceeded.into());
+        }
+
         if let Some(wallet) = schema.wallet(&crypto::hash(self.to.as_bytes())) {
             schema.increase_wallet_balance(wallet, self.amount, &hash);
+            schema.increase_total_supply(self.amount);
             Ok(())
         } else {
             Err(Error::ReceiverNotFound)?
@@ -258,10 +2271,219 @@ impl Transaction for Issue {
     }
 }
 
+impl Transaction for Burn {
+    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
+        let pub_key = &context.author();
+        let hash = context.tx_hash();
+
+        let mut schema = Schema::new(context.fork());
+
+        let wallet = schema
+            .wallet(&crypto::hash(self.wallet.as_bytes()))
+            .ok_or(Error::SenderNotFound)?;
+
+        if !approve_operation(&mut schema, &wallet, &operation_id("burn", self), pub_key)? {
+            return Ok(());
+        }
+
+        if wallet.balance < self.amount {
+            return Err(Error::InsufficientCurrencyAmount.into());
+        }
+
+        schema.decrease_wallet_balance(wallet, self.amount, &hash);
+        schema.decrease_total_supply(self.amount);
+
+        Ok(())
+    }
+}
+
+impl Transaction for AddOwner {
+    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
+        let pub_key = &context.author();
+        let hash = context.tx_hash();
+
+        let mut schema = Schema::new(context.fork());
+
+        let wallet = schema
+            .wallet(&crypto::hash(self.wallet.as_bytes()))
+            .ok_or(Error::SenderNotFound)?;
+
+        if wallet.is_owner(&self.key) {
+            return Err(Error::OwnerAlreadyExists.into());
+        }
+
+        let weight = if self.weight == 0 { 1 } else { self.weight };
+        let mut weights = wallet.weights.clone();
+        if !weights.is_empty() || weight != 1 {
+            weights = wallet.pub_keys.iter().map(|key| wallet.weight_of(key)).collect();
+            weights.push(weight);
+        }
+        let mut pub_keys = wallet.pub_keys.clone();
+        pub_keys.push(self.key);
+        let quorum = wallet.quorum;
+        let quorum_tiers = wallet.quorum_tiers.clone();
+        check_owners(&pub_keys, &weights, quorum, &quorum_tiers)?;
+
+        if approve_operation(&mut schema, &wallet, &operation_id("add_owner", self), pub_key)? {
+            schema.change_wallet_owners(wallet, pub_keys, weights, quorum, quorum_tiers, &hash);
+        }
+        Ok(())
+    }
+}
+
+impl Transaction for RemoveOwner {
+    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
+        let pub_key = &context.author();
+        let hash = context.tx_hash();
+
+        let mut schema = Schema::new(context.fork());
+
+        let wallet = schema
+            .wallet(&crypto::hash(self.wallet.as_bytes()))
+            .ok_or(Error::SenderNotFound)?;
+
+        if !wallet.is_owner(&self.key) {
+            return Err(Error::OwnerNotFound.into());
+        }
+
+        let index = wallet.pub_keys.iter().position(|key| *key == self.key).unwrap();
+        let mut pub_keys = wallet.pub_keys.clone();
+        pub_keys.remove(index);
+        let mut weights = wallet.weights.clone();
+        if !weights.is_empty() {
+            weights.remove(index);
+        }
+        let quorum = wallet.quorum;
+        let quorum_tiers = wallet.quorum_tiers.clone();
+        check_owners(&pub_keys, &weights, quorum, &quorum_tiers)?;
+
+        if approve_operation(&mut schema, &wallet, &operation_id("remove_owner", self), pub_key)? {
+            schema.change_wallet_owners(wallet, pub_keys, weights, quorum, quorum_tiers, &hash);
+        }
+        Ok(())
+    }
+}
+
+impl Transaction for ReplaceOwner {
+    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
+        let pub_key = &context.author();
+        let hash = context.tx_hash();
+
+        let mut schema = Schema::new(context.fork());
+
+        let wallet = schema
+            .wallet(&crypto::hash(self.wallet.as_bytes()))
+            .ok_or(Error::SenderNotFound)?;
+
+        if !wallet.is_owner(&self.old_key) {
+            return Err(Error::OwnerNotFound.into());
+        }
+        if wallet.is_owner(&self.new_key) {
+            return Err(Error::OwnerAlreadyExists.into());
+        }
+
+        let pub_keys = wallet
+            .pub_keys
+            .iter()
+            .map(|key| if *key == self.old_key { self.new_key } else { *key })
+            .collect();
+        let weights = wallet.weights.clone();
+        let quorum = wallet.quorum;
+        let quorum_tiers = wallet.quorum_tiers.clone();
+
+        if approve_operation(&mut schema, &wallet, &operation_id("replace_owner", self), pub_key)? {
+            schema.change_wallet_owners(wallet, pub_keys, weights, quorum, quorum_tiers, &hash);
+        }
+        Ok(())
+    }
+}
+
+impl Transaction for ChangeQuorum {
+    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
+        let pub_key = &context.author();
+        let hash = context.tx_hash();
+
+        let mut schema = Schema::new(context.fork());
+
+        let wallet = schema
+            .wallet(&crypto::hash(self.wallet.as_bytes()))
+            .ok_or(Error::SenderNotFound)?;
+
+        let pub_keys = wallet.pub_keys.clone();
+        let weights = wallet.weights.clone();
+        let quorum_tiers = wallet.quorum_tiers.clone();
+        check_owners(&pub_keys, &weights, self.quorum, &quorum_tiers)?;
+
+        if approve_operation(&mut schema, &wallet, &operation_id("change_quorum", self), pub_key)? {
+            schema.change_wallet_owners(wallet, pub_keys, weights, self.quorum, quorum_tiers, &hash);
+        }
+        Ok(())
+    }
+}
+
+impl Transaction for ChangeQuorumTiers {
+    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
+        let pub_key = &context.author();
+        let hash = context.tx_hash();
+
+        let mut schema = Schema::new(context.fork());
+
+        let wallet = schema
+            .wallet(&crypto::hash(self.wallet.as_bytes()))
+            .ok_or(Error::SenderNotFound)?;
+
+        let pub_keys = wallet.pub_keys.clone();
+        let weights = wallet.weights.clone();
+        let quorum = wallet.quorum;
+        check_owners(&pub_keys, &weights, quorum, &self.quorum_tiers)?;
+
+        let id = operation_id("change_quorum_tiers", self);
+        if approve_operation(&mut schema, &wallet, &id, pub_key)? {
+            let quorum_tiers = self.quorum_tiers.clone();
+            schema.change_wallet_owners(wallet, pub_keys, weights, quorum, quorum_tiers, &hash);
+        }
+        Ok(())
+    }
+}
+
+impl Transaction for SetSpendingLimit {
+    fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
+        let pub_key = &context.author();
+        let hash = context.tx_hash();
+
+        let mut schema = Schema::new(context.fork());
+
+        let wallet = schema
+            .wallet(&crypto::hash(self.wallet.as_bytes()))
+            .ok_or(Error::SenderNotFound)?;
+
+        if self.window != 0 && (self.quorum == 0 || self.quorum > wallet.total_weight()) {
+            return Err(Error::QuorumIsNotCorrect.into());
+        }
+
+        if approve_operation(&mut schema, &wallet, &operation_id("set_spending_limit", self), pub_key)? {
+            // Transfers made under the previous limit still count toward the new one.
+            let spendings = schema
+                .spending_limit(&crypto::hash(self.wallet.as_bytes()))
+                .map(|limit| limit.spendings)
+                .unwrap_or_default();
+            let limit = if self.window == 0 {
+                None
+            } else {
+                Some(SpendingLimit::new(self.limit, self.window, self.quorum, spendings))
+            };
+            schema.set_spending_limit(&self.wallet, limit);
+            schema.append_wallet_history(wallet, &hash);
+        }
+        Ok(())
+    }
+}
+
 impl Transaction for CreateWallet {
     fn execute(&self, mut context: TransactionContext) -> ExecutionResult {
         let pub_key = &context.author();
         let hash = context.tx_hash();
+        let config = ServiceConfig::actual(context.fork());
 
         let mut schema = Schema::new(context.fork());
 
@@ -269,14 +2491,32 @@ impl Transaction for CreateWallet {
             return Err(ExecutionError::new(ERROR_SENDER_WRONG_KEY));
         }
 
+        if self.name.len() > config.max_name_length {
+            return Err(Error::NameTooLong.into());
+        }
+
         let name = &self.name;
         if schema.wallet(&crypto::hash(name.as_bytes())).is_none() {
 
-            if self.quorum == 0 || self.quorum > self.pub_keys.len() as u32 {
-                Err(Error::QuorumIsNotCorrect)?
+            check_owners(&self.pub_keys, &self.weights, self.quorum, &self.quorum_tiers)?;
+
+            let supply = schema.total_supply().get().unwrap_or_default();
+            if supply.checked_add(config.initial_balance).is_none() {
+                return Err(Error::SupplyOverflow.into());
             }
 
-            schema.create_wallet(name, &self.pub_keys, self.quorum, &hash);
+            schema.create_wallet(
+                name,
+                &self.pub_keys,
+                &self.weights,
+                self.quorum,
+                &self.quorum_tiers,
+                config.initial_balance,
+                &hash,
+            );
+            // The fee is paid out of the initial balance of the new wallet, which covers
+            // it in a valid configuration.
+            charge_fee(&mut schema, &config, name, config.fees.fixed_fee("CreateWallet"), &hash)?;
             Ok(())
         } else {
             Err(Error::WalletAlreadyExists)?
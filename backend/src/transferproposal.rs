@@ -1,9 +1,56 @@
 //! Transfer proposal
 
-use exonum::crypto::{Hash, HashStream};
+use exonum::{
+    crypto::{self, Hash, HashStream, PublicKey},
+    helpers::Height,
+    proto::ProtobufConvert,
+};
 
 use super::proto;
 
+/// Lifecycle status of a transfer proposal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProposalStatus {
+    /// Proposal is waiting for approvals.
+    Pending,
+    /// Transfer has been executed.
+    Executed,
+    /// Proposal has been rejected by the wallet owners.
+    Rejected,
+    /// Proposal has expired before reaching the quorum.
+    Expired,
+    /// Proposal has been cancelled.
+    Cancelled,
+    /// Transfer has been approved and waits for its execution height.
+    Scheduled,
+}
+
+impl ProtobufConvert for ProposalStatus {
+    type ProtoStruct = proto::ProposalStatus;
+
+    fn to_pb(&self) -> Self::ProtoStruct {
+        match self {
+            ProposalStatus::Pending => proto::ProposalStatus::PENDING,
+            ProposalStatus::Executed => proto::ProposalStatus::EXECUTED,
+            ProposalStatus::Rejected => proto::ProposalStatus::REJECTED,
+            ProposalStatus::Expired => proto::ProposalStatus::EXPIRED,
+            ProposalStatus::Cancelled => proto::ProposalStatus::CANCELLED,
+            ProposalStatus::Scheduled => proto::ProposalStatus::SCHEDULED,
+        }
+    }
+
+    fn from_pb(pb: Self::ProtoStruct) -> Result<Self, failure::Error> {
+        Ok(match pb {
+            proto::ProposalStatus::PENDING => ProposalStatus::Pending,
+            proto::ProposalStatus::EXECUTED => ProposalStatus::Executed,
+            proto::ProposalStatus::REJECTED => ProposalStatus::Rejected,
+            proto::ProposalStatus::EXPIRED => ProposalStatus::Expired,
+            proto::ProposalStatus::CANCELLED => ProposalStatus::Cancelled,
+            proto::ProposalStatus::SCHEDULED => ProposalStatus::Scheduled,
+        })
+    }
+}
+
 /// Wallet information stored in the database.
 #[derive(Clone, Debug, ProtobufConvert)]
 #[exonum(pb = "proto::TransferProposal", serde_pb_convert)]
@@ -18,44 +65,110 @@ pub struct TransferProposal {
     ///
     /// [idempotence]: https://en.wikipedia.org/wiki/Idempotence
     pub seed: u64,
-    /// Number of signs
-    pub signs: u32
+    /// Keys which have already approved the transfer.
+    pub approvals: Vec<PublicKey>,
+    /// Keys which have rejected the transfer.
+    pub rejections: Vec<PublicKey>,
+    /// Current status of the proposal.
+    pub status: ProposalStatus,
+    /// Last height at which the proposal can be approved.
+    pub valid_until: Height,
+    /// Height at which the approved transfer is executed, `Height(0)` to execute it immediately.
+    pub execute_at: Height,
+    /// Reference of the transfer, such as an invoice id, empty if not set.
+    pub memo: String,
+    /// Id of the invoice paid by the transfer, `Hash::zero()` if the transfer doesn't pay
+    /// an invoice.
+    pub invoice_id: Hash,
+    /// Name of the transferred asset, empty for the native currency.
+    pub asset: String,
 }
 
 impl TransferProposal {
     /// Create new Wallet.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         from: &str,
         to: &str,
         amount: u64,
         seed: u64,
-        signs: u32,
+        approvals: Vec<PublicKey>,
+        valid_until: Height,
+        execute_at: Height,
+        memo: &str,
     ) -> Self {
         Self {
             from: from.to_owned(),
             to: to.to_owned(),
             amount,
             seed,
-            signs,
+            approvals,
+            rejections: Vec::new(),
+            status: ProposalStatus::Pending,
+            valid_until,
+            execute_at,
+            memo: memo.to_owned(),
+            invoice_id: Hash::zero(),
+            asset: String::new(),
+        }
+    }
+    /// Returns `true` if the given key has already approved the transfer.
+    pub fn is_approved_by(&self, key: &PublicKey) -> bool {
+        self.approvals.contains(key)
+    }
+    /// Returns `true` if the given key has already rejected the transfer.
+    pub fn is_rejected_by(&self, key: &PublicKey) -> bool {
+        self.rejections.contains(key)
+    }
+    /// Returns `true` if the proposal can no longer be approved at the given height.
+    pub fn is_expired_at(&self, height: Height) -> bool {
+        self.status == ProposalStatus::Pending && height > self.valid_until
+    }
+    /// Returns `true` if the scheduled transfer has to be executed at the given height.
+    pub fn is_due_at(&self, height: Height) -> bool {
+        self.status == ProposalStatus::Scheduled && height >= self.execute_at
+    }
+    /// Returns a copy of this proposal with the approval of the given key added.
+    pub fn add_approval(self, key: &PublicKey) -> Self {
+        let mut approvals = self.approvals;
+        approvals.push(*key);
+        Self { approvals, ..self }
+    }
+    /// Returns a copy of this proposal with the rejection of the given key added.
+    pub fn add_rejection(self, key: &PublicKey) -> Self {
+        let mut rejections = self.rejections;
+        rejections.push(*key);
+        Self { rejections, ..self }
+    }
+    /// Returns a copy of this proposal paying the invoice with the given id.
+    pub fn set_invoice(self, invoice_id: &Hash) -> Self {
+        Self {
+            invoice_id: *invoice_id,
+            ..self
         }
     }
-    /// Returns a copy of this wallet with updated balance.
-    pub fn increase_signs(self) -> Self {
-        Self::new(
-            &self.from,
-            &self.to,
-            self.amount,
-            self.seed,
-            self.signs + 1
-        )
+    /// Returns a copy of this proposal transferring the given asset.
+    pub fn set_asset(self, asset: &str) -> Self {
+        Self {
+            asset: asset.to_owned(),
+            ..self
+        }
+    }
+    /// Returns a copy of this proposal with the given status.
+    pub fn set_status(self, status: ProposalStatus) -> Self {
+        Self { status, ..self }
     }
     /// Returns hash
     pub fn hash(&self) -> Hash {
-        return HashStream::new()
+        let mut stream = HashStream::new()
             .update(&self.from.as_bytes())
             .update(&self.to.as_bytes())
             .update(&self.amount.to_le_bytes())
-            .update(&self.seed.to_le_bytes())
-            .hash();
+            .update(&self.seed.to_le_bytes());
+        // Transfers of the native currency keep the ids they had before assets were added.
+        if !self.asset.is_empty() {
+            stream = stream.update(crypto::hash(self.asset.as_bytes()).as_ref());
+        }
+        stream.update(&self.memo.as_bytes()).hash()
     }
 }
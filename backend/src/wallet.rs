@@ -18,6 +18,16 @@ use exonum::crypto::{Hash, PublicKey};
 
 use super::proto;
 
+/// Quorum required for transfers of at most `max_amount`.
+#[derive(Clone, Debug, PartialEq, ProtobufConvert)]
+#[exonum(pb = "proto::QuorumTier", serde_pb_convert)]
+pub struct QuorumTier {
+    /// Largest transfer amount covered by the tier.
+    pub max_amount: u64,
+    /// Quorum size for transfers covered by the tier.
+    pub quorum: u32,
+}
+
 /// Wallet information stored in the database.
 #[derive(Clone, Debug, ProtobufConvert)]
 #[exonum(pb = "proto::Wallet", serde_pb_convert)]
@@ -26,8 +36,12 @@ pub struct Wallet {
     pub name: String,
     /// `PublicKey` of the wallet.
     pub pub_keys: Vec<PublicKey>,
-    /// Quorum number.
+    /// Weights of the public keys, empty if every key has the weight of 1.
+    pub weights: Vec<u32>,
+    /// Quorum number, i.e. the total weight of approvals required.
     pub quorum: u32,
+    /// Quorums for smaller transfers, ordered by `max_amount`.
+    pub quorum_tiers: Vec<QuorumTier>,
     /// Current balance of the wallet.
     pub balance: u64,
     /// Length of the transactions history.
@@ -38,10 +52,13 @@ pub struct Wallet {
 
 impl Wallet {
     /// Create new Wallet.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         name: &str,
         pub_keys: Vec<PublicKey>,
+        weights: Vec<u32>,
         quorum: u32,
+        quorum_tiers: Vec<QuorumTier>,
         balance: u64,
         history_len: u64,
         &history_hash: &Hash,
@@ -49,7 +66,9 @@ impl Wallet {
         Self {
             name: name.to_owned(),
             pub_keys,
+            weights,
             quorum,
+            quorum_tiers,
             balance,
             history_len,
             history_hash,
@@ -60,10 +79,68 @@ impl Wallet {
         Self::new(
             &self.name,
             self.pub_keys,
+            self.weights,
             self.quorum,
+            self.quorum_tiers,
             balance,
             self.history_len + 1,
             history_hash,
         )
     }
+    /// Returns a copy of this wallet with updated history.
+    pub fn set_history_hash(self, history_hash: &Hash) -> Self {
+        let balance = self.balance;
+        self.set_balance(balance, history_hash)
+    }
+    /// Returns a copy of this wallet with updated owners and quorums.
+    pub fn set_owners(
+        self,
+        pub_keys: Vec<PublicKey>,
+        weights: Vec<u32>,
+        quorum: u32,
+        quorum_tiers: Vec<QuorumTier>,
+        history_hash: &Hash,
+    ) -> Self {
+        Self::new(
+            &self.name,
+            pub_keys,
+            weights,
+            quorum,
+            quorum_tiers,
+            self.balance,
+            self.history_len + 1,
+            history_hash,
+        )
+    }
+    /// Returns `true` if the given key is one of the wallet owners.
+    pub fn is_owner(&self, key: &PublicKey) -> bool {
+        self.pub_keys.contains(key)
+    }
+    /// Returns the weight of the given key, or 0 if it isn't one of the wallet owners.
+    pub fn weight_of(&self, key: &PublicKey) -> u32 {
+        match self.pub_keys.iter().position(|owner| owner == key) {
+            Some(index) => self.weights.get(index).cloned().unwrap_or(1),
+            None => 0,
+        }
+    }
+    /// Returns the total weight of the wallet owners.
+    pub fn total_weight(&self) -> u32 {
+        self.pub_keys.iter().map(|key| self.weight_of(key)).sum()
+    }
+    /// Returns the total weight of wallet owners among the given (distinct) keys.
+    pub fn count_approvals(&self, approvals: &[PublicKey]) -> u32 {
+        approvals.iter().map(|key| self.weight_of(key)).sum()
+    }
+    /// Returns the quorum required for a transfer of the given amount.
+    pub fn transfer_quorum(&self, amount: u64) -> u32 {
+        self.quorum_tiers
+            .iter()
+            .find(|tier| amount <= tier.max_amount)
+            .map_or(self.quorum, |tier| tier.quorum)
+    }
+    /// Returns `true` if the given quorum can no longer be reached once the given
+    /// (distinct) keys have rejected.
+    pub fn is_quorum_unreachable(&self, rejections: &[PublicKey], quorum: u32) -> bool {
+        self.total_weight() - self.count_approvals(rejections) < quorum
+    }
 }
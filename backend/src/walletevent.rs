@@ -0,0 +1,76 @@
+//! Change of a wallet made by the service
+
+use exonum::{crypto::Hash, helpers::Height, proto::ProtobufConvert};
+
+use super::proto;
+
+/// Kind of the change of a wallet made by the service rather than by a transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WalletEventKind {
+    /// Transfer proposal has expired before reaching the quorum.
+    ProposalExpired,
+    /// Subscription payment has been made.
+    SubscriptionPaid,
+    /// Subscription payment has been skipped for the lack of funds.
+    SubscriptionSkipped,
+    /// Scheduled transfer has been executed at its execution height.
+    ScheduledTransferExecuted,
+    /// Wallet has been created in the genesis block.
+    GenesisWalletCreated,
+}
+
+impl ProtobufConvert for WalletEventKind {
+    type ProtoStruct = proto::WalletEventKind;
+
+    fn to_pb(&self) -> Self::ProtoStruct {
+        match self {
+            WalletEventKind::ProposalExpired => proto::WalletEventKind::PROPOSAL_EXPIRED,
+            WalletEventKind::SubscriptionPaid => proto::WalletEventKind::SUBSCRIPTION_PAID,
+            WalletEventKind::SubscriptionSkipped => proto::WalletEventKind::SUBSCRIPTION_SKIPPED,
+            WalletEventKind::ScheduledTransferExecuted => {
+                proto::WalletEventKind::SCHEDULED_TRANSFER_EXECUTED
+            }
+            WalletEventKind::GenesisWalletCreated => proto::WalletEventKind::GENESIS_WALLET_CREATED,
+        }
+    }
+
+    fn from_pb(pb: Self::ProtoStruct) -> Result<Self, failure::Error> {
+        Ok(match pb {
+            proto::WalletEventKind::PROPOSAL_EXPIRED => WalletEventKind::ProposalExpired,
+            proto::WalletEventKind::SUBSCRIPTION_PAID => WalletEventKind::SubscriptionPaid,
+            proto::WalletEventKind::SUBSCRIPTION_SKIPPED => WalletEventKind::SubscriptionSkipped,
+            proto::WalletEventKind::SCHEDULED_TRANSFER_EXECUTED => {
+                WalletEventKind::ScheduledTransferExecuted
+            }
+            proto::WalletEventKind::GENESIS_WALLET_CREATED => WalletEventKind::GenesisWalletCreated,
+        })
+    }
+}
+
+/// Change of a wallet made by the service in `before_commit` or in the genesis block,
+/// which is recorded in the wallet history in place of a transaction.
+#[derive(Clone, Debug, ProtobufConvert)]
+#[exonum(pb = "proto::WalletEvent", serde_pb_convert)]
+pub struct WalletEvent {
+    /// Kind of the event.
+    pub kind: WalletEventKind,
+    /// Id of the transfer proposal or the subscription which caused the event, or the
+    /// hash of the wallet name for a genesis wallet.
+    pub id: Hash,
+    /// Height of the block with the event.
+    pub height: Height,
+    /// Amount of currency moved by the event, 0 if no currency was moved.
+    pub amount: u64,
+}
+
+impl WalletEvent {
+    /// Create new wallet event.
+    pub fn new(kind: WalletEventKind, id: &Hash, height: Height, amount: u64) -> Self {
+        Self {
+            kind,
+            id: *id,
+            height,
+            amount,
+        }
+    }
+}
@@ -23,21 +23,40 @@ extern crate serde_json;
 
 use exonum::{
     api::node::public::explorer::{TransactionQuery, TransactionResponse},
-    crypto::{self, Hash, PublicKey, SecretKey},
+    crypto::{self, CryptoHash, Hash, PublicKey, SecretKey},
+    helpers::Height,
     messages::{self, RawTransaction, Signed},
 };
 use exonum_testkit::{ApiKind, TestKit, TestKitApi, TestKitBuilder};
 
+use std::collections::BTreeMap;
+
 // Import data types used in tests from the crate where the service is defined.
 use exonum_cryptocurrency_multisig::{
-    api::{WalletInfo, WalletQuery},
-    transactions::{CreateWallet, Transfer},
-    wallet::Wallet,
-    Service,
+    allowance::{allowance_key, Allowance},
+    api::{
+        AllowanceInfo, AllowanceQuery, AssetInfo, AssetQuery, AwaitingProposalsInfo, BalanceInfo,
+        BalanceQuery, FeeInfo, FeeQuery, HtlcInfo, HtlcQuery, InvoicesQuery, KeyQuery,
+        KeyWalletsInfo, OpenInvoice, PendingProposal, PendingProposalsInfo, PendingProposalsQuery,
+        ProposalInfo, ProposalProof, ProposalQuery, SupplyInfo, WalletHistory, WalletInfo,
+        WalletProposals, WalletQuery,
+    },
+    asset::{asset_balance_key, Asset},
+    config::{FeeSchedule, GenesisWallet},
+    htlc::{Htlc, HtlcStatus},
+    transactions::{
+        AddOwner, Approve, ApproveTransfer, BatchTransfer, Burn, CancelSubscription, CancelTransfer,
+        ChangeQuorum, ChangeQuorumTiers, CreateAsset, CreateInvoice, CreateSubscription, CreateWallet, HtlcClaim, HtlcLock, HtlcRefund, Issue, PayInvoice, ProposeTransfer, RejectTransfer, RemoveOwner,
+        ReplaceOwner, SetSpendingLimit, Transfer, TransferFrom, TransferOutput,
+    },
+    transferproposal::{ProposalStatus, TransferProposal},
+    wallet::{QuorumTier, Wallet},
+    walletevent::{WalletEvent, WalletEventKind},
+    GenesisConfig, Schema, Service, ServiceConfig,
 };
 
 // Imports shared test constants.
-use crate::constants::{ALICE_NAME, BOB_NAME};
+use crate::constants::{ALICE_NAME, BOB_NAME, CAROL_NAME};
 
 mod constants;
 
@@ -455,108 +474,2788 @@ fn test_transfer_two_of_three_keys() {
     assert_eq!(wallet.balance, 110);
 }
 
-/// Wrapper for the cryptocurrency service API allowing to easily use it
-/// (compared to `TestKitApi` calls).
-struct CryptocurrencyApi {
-    pub inner: TestKitApi,
+/// Check that an owner who has already approved a transfer cannot approve it again.
+#[test]
+fn test_transfer_already_approved() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 3, 3);
+    let (tx_bob, _, _) = api.create_wallet(BOB_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+
+    let transfer = |index: usize| {
+        Transfer::sign(
+            ALICE_NAME.to_string(),
+            BOB_NAME.to_string(),
+            10, // transferred amount
+            0,  // seed
+            &pubkeys_alice[index],
+            &keys_alice[index],
+        )
+    };
+    let tx = transfer(1);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    // The first owner approves the proposal created by the transfer of the second one.
+    let proposal_id = TransferProposal::new(
+        ALICE_NAME,
+        BOB_NAME,
+        10,
+        0,
+        vec![],
+        Height(0),
+        Height(0),
+        "",
+    )
+    .hash();
+    let tx = ApproveTransfer::sign(&proposal_id, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    // The same transfer submitted by the first owner is a second approval of this owner.
+    let tx = transfer(0);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 6, "description": "Already approved by this key" }),
+    );
+
+    let (proposal, _) = api.get_proposal(proposal_id);
+    let proposal = proposal.unwrap();
+    assert_eq!(proposal.approvals, vec![pubkeys_alice[1], pubkeys_alice[0]]);
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 100);
+    let wallet = api.get_wallet(BOB_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 100);
 }
 
-impl CryptocurrencyApi {
-    /// Generates a wallet creation transaction with a random key pair, sends it over HTTP,
-    /// and checks the synchronous result (i.e., the hash of the transaction returned
-    /// within the response).
-    /// Note that the transaction is not immediately added to the blockchain, but rather is put
-    /// to the pool of unconfirmed transactions.
-    fn create_wallet(&self, name: &str, key_num: u32, quorum: u32) -> (Signed<RawTransaction>, Vec<PublicKey>, Vec<SecretKey>) {
-        if quorum > key_num {
-            panic!("Qurum should less or equal to key_num");
-        }
+/// Check that an explicit transfer proposal is executed once enough distinct owners approve it.
+#[test]
+fn test_propose_and_approve_transfer() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 3, 2);
+    let (tx_bob, _, _) = api.create_wallet(BOB_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
 
-        let mut pub_keys: Vec<PublicKey> = Vec::with_capacity(key_num as usize);
-        let mut keys : Vec<SecretKey> = Vec::with_capacity(key_num as usize);
-        for _ in 0..key_num {
-            let (pubkey, key) = crypto::gen_keypair();
-            pub_keys.push(pubkey);
-            keys.push(key);
-        }
+    // The hash of the proposal transaction is the id of the proposal.
+    let tx_propose = ProposeTransfer::sign(
+        ALICE_NAME.to_string(),
+        BOB_NAME.to_string(),
+        10,        // transferred amount
+        0,         // seed
+        Height(0), // default lifetime
+        &pubkeys_alice[0],
+        &keys_alice[0],
+    );
+    api.transfer(&tx_propose);
+    testkit.create_block();
+    api.assert_tx_status(tx_propose.hash(), &json!({ "type": "success" }));
 
-        // Create a pre-signed transaction
-        let tx = CreateWallet::sign(name, pub_keys.clone(), quorum,&pub_keys[0], &keys[0]);
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 100);
 
-        let data = messages::to_hex_string(&tx);
-        let tx_info: TransactionResponse = self
-            .inner
-            .public(ApiKind::Explorer)
-            .query(&json!({ "tx_body": data }))
-            .post("v1/transactions")
-            .unwrap();
-        assert_eq!(tx_info.tx_hash, tx.hash());
-        (tx, pub_keys, keys)
-    }
+    // The proposer cannot approve the same proposal for the second time.
+    let tx = ApproveTransfer::sign(&tx_propose.hash(), &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 6, "description": "Already approved by this key" }),
+    );
 
-    fn get_wallet(&self, name: String) -> Option<Wallet> {
-        let wallet_info = self
-            .inner
-            .public(ApiKind::Service("cryptocurrency"))
-            .query(&WalletQuery { name: name.clone() })
-            .get::<WalletInfo>("v1/wallets/info")
-            .unwrap();
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 100);
 
-        let to_wallet = wallet_info.wallet_proof.to_wallet.check().unwrap();
-        let wallet = to_wallet
-            .all_entries()
-            .find(|(ref k, _)| **k == crypto::hash(name.as_bytes()))
-            .and_then(|tuple| tuple.1)
-            .cloned();
-        wallet
-    }
+    let tx = ApproveTransfer::sign(&tx_propose.hash(), &pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
 
-    /// Sends a transfer transaction over HTTP and checks the synchronous result.
-    fn transfer(&self, tx: &Signed<RawTransaction>) {
-        let data = messages::to_hex_string(&tx);
-        let tx_info: TransactionResponse = self
-            .inner
-            .public(ApiKind::Explorer)
-            .query(&json!({ "tx_body": data }))
-            .post("v1/transactions")
-            .unwrap();
-        assert_eq!(tx_info.tx_hash, tx.hash());
-    }
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 90);
+    let wallet = api.get_wallet(BOB_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 110);
 
-    /// Asserts that a wallet with the specified public key is not known to the blockchain.
-    fn assert_no_wallet(&self, name: String) {
-        let wallet_info: WalletInfo = self
-            .inner
-            .public(ApiKind::Service("cryptocurrency"))
-            .query(&WalletQuery { name: name.clone() })
-            .get("v1/wallets/info")
-            .unwrap();
+    // Once executed, the proposal cannot be approved any more.
+    let tx = ApproveTransfer::sign(&tx_propose.hash(), &pubkeys_alice[2], &keys_alice[2]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 9, "description": "Transfer proposal is not pending" }),
+    );
 
-        let to_wallet = wallet_info.wallet_proof.to_wallet.check().unwrap();
-        assert!(to_wallet.missing_keys().find(|v| **v == crypto::hash(name.as_bytes())).is_some())
-    }
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 90);
+}
 
-    /// Asserts that the transaction with the given hash has a specified status.
-    fn assert_tx_status(&self, tx_hash: Hash, expected_status: &serde_json::Value) {
-        let info: serde_json::Value = self
-            .inner
-            .public(ApiKind::Explorer)
-            .query(&TransactionQuery::new(tx_hash))
-            .get("v1/transactions")
-            .unwrap();
+/// Check that a transfer proposal cannot be approved after its `valid_until` height.
+#[test]
+fn test_transfer_proposal_expiry() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 2, 2);
+    let (tx_bob, _, _) = api.create_wallet(BOB_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
 
-        if let serde_json::Value::Object(mut info) = info {
-            let tx_status = info.remove("status").unwrap();
-            assert_eq!(tx_status, *expected_status);
-        } else {
-            panic!("Invalid transaction info format, object expected");
-        }
-    }
+    // The proposal can only be approved in the block which includes it.
+    let tx_propose = ProposeTransfer::sign(
+        ALICE_NAME.to_string(),
+        BOB_NAME.to_string(),
+        10, // transferred amount
+        0,  // seed
+        testkit.height().next(),
+        &pubkeys_alice[0],
+        &keys_alice[0],
+    );
+    api.transfer(&tx_propose);
+    testkit.create_block();
+    api.assert_tx_status(tx_propose.hash(), &json!({ "type": "success" }));
+
+    let tx = ApproveTransfer::sign(&tx_propose.hash(), &pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 10, "description": "Transfer proposal has expired" }),
+    );
+
+    // The expiry is recorded in the history of the sender's wallet.
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 100);
+    assert_eq!(wallet.history_len, 2);
+    let events = api.get_wallet_events(ALICE_NAME);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].kind, WalletEventKind::ProposalExpired);
+    assert_eq!(events[0].id, tx_propose.hash());
+    assert_eq!(events[0].amount, 0);
+    assert_eq!(api.get_wallet_history(ALICE_NAME).transactions.len(), 1);
+    let wallet = api.get_wallet(BOB_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 100);
 }
 
-/// Creates a testkit together with the API wrapper defined above.
-fn create_testkit() -> (TestKit, CryptocurrencyApi) {
-    let testkit = TestKitBuilder::validator().with_service(Service).create();
+/// Check that transfer proposals are provable from the block state hash.
+#[test]
+fn test_proposal_proofs() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 2, 2);
+    let (tx_bob, _, _) = api.create_wallet(BOB_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+
+    let tx_propose = ProposeTransfer::sign(
+        ALICE_NAME.to_string(),
+        BOB_NAME.to_string(),
+        10,        // transferred amount
+        0,         // seed
+        Height(0), // default lifetime
+        &pubkeys_alice[0],
+        &keys_alice[0],
+    );
+    api.transfer(&tx_propose);
+    testkit.create_block();
+    api.assert_tx_status(tx_propose.hash(), &json!({ "type": "success" }));
+
+    let (pending, archived) = api.get_proposal(tx_propose.hash());
+    let pending = pending.unwrap();
+    assert_eq!(pending.status, ProposalStatus::Pending);
+    assert_eq!(pending.approvals, vec![pubkeys_alice[0]]);
+    assert!(archived.is_none());
+
+    let tx = ApproveTransfer::sign(&tx_propose.hash(), &pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    let (pending, archived) = api.get_proposal(tx_propose.hash());
+    assert!(pending.is_none());
+    let archived = archived.unwrap();
+    assert_eq!(archived.status, ProposalStatus::Executed);
+    assert_eq!(archived.approvals, pubkeys_alice);
+}
+
+/// Check that currency can be issued only by the configured issuers.
+#[test]
+fn test_issue() {
+    let (issuer_pk, issuer_sk) = crypto::gen_keypair();
+    let (mut testkit, api) = create_testkit_with_service(Service::new(ServiceConfig {
+        issuers: vec![issuer_pk],
+        ..ServiceConfig::default()
+    }));
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+
+    let tx = Issue::sign(ALICE_NAME.to_string(), 10, 0, &issuer_pk, &issuer_sk);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 110);
+
+    // Wallet owners cannot mint currency for themselves.
+    let tx = Issue::sign(ALICE_NAME.to_string(), 10, 1, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 11, "description": "Issuer is not authorized" }),
+    );
+
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 110);
+
+    // Issuance cannot overflow the total supply.
+    let tx = Issue::sign(ALICE_NAME.to_string(), u64::max_value(), 2, &issuer_pk, &issuer_sk);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 37, "description": "Total supply overflow" }),
+    );
+    assert_eq!(api.get_total_supply(), 110);
+}
+
+/// Check that nobody can issue currency when no issuers are configured.
+#[test]
+fn test_issue_without_issuers() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+
+    let tx = Issue::sign(ALICE_NAME.to_string(), 10, 0, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 11, "description": "Issuer is not authorized" }),
+    );
+
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 100);
+}
+
+/// Check that the total supply follows issuance and respects the maximum supply.
+#[test]
+fn test_total_supply() {
+    let (issuer_pk, issuer_sk) = crypto::gen_keypair();
+    let (mut testkit, api) = create_testkit_with_service(Service::new(ServiceConfig {
+        issuers: vec![issuer_pk],
+        max_supply: Some(250),
+        ..ServiceConfig::default()
+    }));
+    assert_eq!(api.get_total_supply(), 0);
+
+    let (tx_alice, _, _) = api.create_wallet(ALICE_NAME, 1, 1);
+    let (tx_bob, _, _) = api.create_wallet(BOB_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+    assert_eq!(api.get_total_supply(), 200);
+
+    let tx = Issue::sign(ALICE_NAME.to_string(), 50, 0, &issuer_pk, &issuer_sk);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    assert_eq!(api.get_total_supply(), 250);
+
+    let tx = Issue::sign(ALICE_NAME.to_string(), 1, 1, &issuer_pk, &issuer_sk);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 12, "description": "Maximum supply exceeded" }),
+    );
+    assert_eq!(api.get_total_supply(), 250);
+
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 150);
+}
+
+/// Check that burning requires the quorum of the wallet owners.
+#[test]
+fn test_burn() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 2, 2);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+
+    let tx = Burn::sign(ALICE_NAME.to_string(), 30, 0, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 100);
+    assert_eq!(api.get_total_supply(), 100);
+
+    let tx = Burn::sign(ALICE_NAME.to_string(), 30, 0, &pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 70);
+    assert_eq!(api.get_total_supply(), 70);
+}
+
+/// Check that pending wallet operations are unlisted once executed, expire and are dropped
+/// when the owners change.
+#[test]
+fn test_pending_operation_expiry() {
+    let (mut testkit, api) = create_testkit_with_service(Service::new(ServiceConfig {
+        proposal_lifetime: 2,
+        ..ServiceConfig::default()
+    }));
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 2, 2);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    let wallet_operations = |testkit: &TestKit| {
+        Schema::new(testkit.snapshot())
+            .wallet_operations(&crypto::hash(ALICE_NAME.as_bytes()))
+            .len()
+    };
+
+    // The executed operation is no longer listed.
+    let tx = Burn::sign(ALICE_NAME.to_string(), 30, 2, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    assert_eq!(wallet_operations(&testkit), 1);
+    let tx = Burn::sign(ALICE_NAME.to_string(), 30, 2, &pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    assert_eq!(wallet_operations(&testkit), 0);
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 70);
+
+    // The approval is dropped once the lifetime has passed.
+    let tx = Burn::sign(ALICE_NAME.to_string(), 30, 0, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    assert_eq!(wallet_operations(&testkit), 1);
+    testkit.create_blocks_until(Height(testkit.height().0 + 2));
+    assert_eq!(wallet_operations(&testkit), 0);
+
+    let tx = Burn::sign(ALICE_NAME.to_string(), 30, 0, &pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 70);
+
+    // Approvals collected before the owners change are dropped.
+    let tx = Burn::sign(ALICE_NAME.to_string(), 30, 1, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    let (new_pk, _) = crypto::gen_keypair();
+    let tx = AddOwner::sign(ALICE_NAME.to_string(), &new_pk, 0, 0, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    let tx = AddOwner::sign(ALICE_NAME.to_string(), &new_pk, 0, 0, &pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    let tx = Burn::sign(ALICE_NAME.to_string(), 30, 1, &pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.pub_keys.len(), 3);
+    assert_eq!(wallet.balance, 70);
+    assert_eq!(wallet_operations(&testkit), 1);
+}
+
+/// Check that a batch transfer approved by the quorum pays all the receivers at once.
+#[test]
+fn test_batch_transfer() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 2, 2);
+    let (tx_bob, _, _) = api.create_wallet(BOB_NAME, 1, 1);
+    let (tx_carol, _, _) = api.create_wallet(CAROL_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_carol.hash(), &json!({ "type": "success" }));
+
+    let outputs = vec![TransferOutput::new(BOB_NAME, 30), TransferOutput::new(CAROL_NAME, 20)];
+    let tx = BatchTransfer::sign(ALICE_NAME.to_string(), outputs.clone(), 0, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    assert_eq!(api.get_wallet(ALICE_NAME.to_string()).unwrap().balance, 100);
+
+    let tx = BatchTransfer::sign(ALICE_NAME.to_string(), outputs, 0, &pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 50);
+    let wallet = api.get_wallet(BOB_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 130);
+    assert_eq!(wallet.history_len, 2);
+    let wallet = api.get_wallet(CAROL_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 120);
+    assert_eq!(wallet.history_len, 2);
+
+    // The batch fails as a whole if any of the receivers doesn't exist.
+    let outputs = vec![TransferOutput::new(BOB_NAME, 10), TransferOutput::new("Dave", 10)];
+    let tx = BatchTransfer::sign(ALICE_NAME.to_string(), outputs, 1, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 2, "description": "Receiver doesn't exist" }),
+    );
+
+    // ... or if the total amount exceeds the balance of the sender.
+    let outputs = vec![TransferOutput::new(BOB_NAME, 30), TransferOutput::new(CAROL_NAME, 30)];
+    let tx = BatchTransfer::sign(ALICE_NAME.to_string(), outputs.clone(), 2, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    let tx = BatchTransfer::sign(ALICE_NAME.to_string(), outputs, 2, &pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 3, "description": "Insufficient currency amount" }),
+    );
+
+    assert_eq!(api.get_wallet(ALICE_NAME.to_string()).unwrap().balance, 50);
+    assert_eq!(api.get_wallet(BOB_NAME.to_string()).unwrap().balance, 130);
+    assert_eq!(api.get_wallet(CAROL_NAME.to_string()).unwrap().balance, 120);
+}
+
+/// Check that batch transfers within the spending limit need only the lower quorum.
+#[test]
+fn test_batch_transfer_spending_limit() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 3, 3);
+    let (tx_bob, _, _) = api.create_wallet(BOB_NAME, 1, 1);
+    let (tx_carol, _, _) = api.create_wallet(CAROL_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_carol.hash(), &json!({ "type": "success" }));
+
+    for (pk, sk) in pubkeys_alice.iter().zip(&keys_alice) {
+        let tx = SetSpendingLimit::sign(ALICE_NAME.to_string(), 50, 5, 1, 0, pk, sk);
+        api.transfer(&tx);
+    }
+    testkit.create_block();
+
+    let outputs = vec![TransferOutput::new(BOB_NAME, 20), TransferOutput::new(CAROL_NAME, 20)];
+    let tx = BatchTransfer::sign(ALICE_NAME.to_string(), outputs, 0, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    assert_eq!(api.get_wallet(ALICE_NAME.to_string()).unwrap().balance, 60);
+
+    // The rest of the limit is too small, so the batch needs the full quorum.
+    let outputs = vec![TransferOutput::new(BOB_NAME, 10), TransferOutput::new(CAROL_NAME, 10)];
+    let tx = BatchTransfer::sign(ALICE_NAME.to_string(), outputs.clone(), 1, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    assert_eq!(api.get_wallet(ALICE_NAME.to_string()).unwrap().balance, 60);
+
+    for (pk, sk) in pubkeys_alice.iter().zip(&keys_alice).skip(1) {
+        let tx = BatchTransfer::sign(ALICE_NAME.to_string(), outputs.clone(), 1, pk, sk);
+        api.transfer(&tx);
+    }
+    testkit.create_block();
+    assert_eq!(api.get_wallet(ALICE_NAME.to_string()).unwrap().balance, 40);
+    assert_eq!(api.get_wallet(BOB_NAME.to_string()).unwrap().balance, 130);
+    assert_eq!(api.get_wallet(CAROL_NAME.to_string()).unwrap().balance, 130);
+}
+
+/// Check that the wallet owners and quorum can be changed by the quorum of the owners.
+#[test]
+fn test_owner_management() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 2, 2);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+
+    // Add a new owner, which requires both current owners.
+    let (new_pk, new_sk) = crypto::gen_keypair();
+    let tx = AddOwner::sign(ALICE_NAME.to_string(), &new_pk, 0, 0, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.pub_keys.len(), 2);
+
+    let tx = AddOwner::sign(ALICE_NAME.to_string(), &new_pk, 0, 0, &pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.pub_keys, vec![pubkeys_alice[0], pubkeys_alice[1], new_pk]);
+    assert_eq!(wallet.quorum, 2);
+    assert_eq!(wallet.history_len, 2);
+
+    // The quorum can't exceed the number of owners.
+    let tx = ChangeQuorum::sign(ALICE_NAME.to_string(), 4, 0, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 5, "description": "Quorum is not correct" }),
+    );
+
+    // Remove the second owner.
+    let tx = RemoveOwner::sign(ALICE_NAME.to_string(), &pubkeys_alice[1], 0, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    let tx = RemoveOwner::sign(ALICE_NAME.to_string(), &pubkeys_alice[1], 0, &new_pk, &new_sk);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.pub_keys, vec![pubkeys_alice[0], new_pk]);
+    assert_eq!(wallet.history_len, 3);
+
+    // The removed owner can no longer approve wallet operations.
+    let tx = ChangeQuorum::sign(ALICE_NAME.to_string(), 1, 0, &pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 1, "description": "" }),
+    );
+
+    // Replace the new owner.
+    let (other_pk, _) = crypto::gen_keypair();
+    let tx = ReplaceOwner::sign(ALICE_NAME.to_string(), &new_pk, &other_pk, 0, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    let tx = ReplaceOwner::sign(ALICE_NAME.to_string(), &new_pk, &other_pk, 0, &new_pk, &new_sk);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    let tx = RemoveOwner::sign(ALICE_NAME.to_string(), &new_pk, 1, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 14, "description": "Key is not an owner of the wallet" }),
+    );
+
+    // A quorum change approved by a single owner stays pending.
+    let tx = ChangeQuorum::sign(ALICE_NAME.to_string(), 1, 0, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.pub_keys, vec![pubkeys_alice[0], other_pk]);
+    assert_eq!(wallet.quorum, 2);
+    assert_eq!(wallet.history_len, 4);
+}
+
+/// Check that approvals of a weighted wallet are counted by the weights of the owners.
+#[test]
+fn test_weighted_wallet() {
+    let (mut testkit, api) = create_testkit();
+    let (cfo_pk, cfo_sk) = crypto::gen_keypair();
+    let (clerk1_pk, clerk1_sk) = crypto::gen_keypair();
+    let (clerk2_pk, clerk2_sk) = crypto::gen_keypair();
+    let pub_keys = vec![cfo_pk, clerk1_pk, clerk2_pk];
+
+    let tx_bad_weights = CreateWallet::sign_weighted(
+        ALICE_NAME,
+        pub_keys.clone(),
+        vec![2],
+        3,
+        Vec::new(),
+        &cfo_pk,
+        &cfo_sk,
+    );
+    api.transfer(&tx_bad_weights);
+    let tx_bad_quorum = CreateWallet::sign_weighted(
+        ALICE_NAME,
+        pub_keys.clone(),
+        vec![2, 1, 1],
+        5,
+        Vec::new(),
+        &cfo_pk,
+        &cfo_sk,
+    );
+    api.transfer(&tx_bad_quorum);
+    // The total weight must fit into `u32`.
+    let tx_overflow_weights = CreateWallet::sign_weighted(
+        ALICE_NAME,
+        pub_keys.clone(),
+        vec![u32::max_value(), 1, 1],
+        3,
+        Vec::new(),
+        &cfo_pk,
+        &cfo_sk,
+    );
+    api.transfer(&tx_overflow_weights);
+    let tx_duplicate_keys = CreateWallet::sign_weighted(
+        ALICE_NAME,
+        vec![cfo_pk, clerk1_pk, cfo_pk],
+        vec![2, 1, 1],
+        3,
+        Vec::new(),
+        &cfo_pk,
+        &cfo_sk,
+    );
+    api.transfer(&tx_duplicate_keys);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx_bad_weights.hash(),
+        &json!({ "type": "error", "code": 15, "description": "Weights are not correct" }),
+    );
+    api.assert_tx_status(
+        tx_bad_quorum.hash(),
+        &json!({ "type": "error", "code": 5, "description": "Quorum is not correct" }),
+    );
+    api.assert_tx_status(
+        tx_overflow_weights.hash(),
+        &json!({ "type": "error", "code": 15, "description": "Weights are not correct" }),
+    );
+    api.assert_tx_status(
+        tx_duplicate_keys.hash(),
+        &json!({ "type": "error", "code": 13, "description": "Key is already an owner of the wallet" }),
+    );
+
+    let tx_alice = CreateWallet::sign_weighted(
+        ALICE_NAME,
+        pub_keys.clone(),
+        vec![2, 1, 1],
+        3,
+        Vec::new(),
+        &cfo_pk,
+        &cfo_sk,
+    );
+    api.transfer(&tx_alice);
+    let (tx_bob, _, _) = api.create_wallet(BOB_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.weights, vec![2, 1, 1]);
+    assert_eq!(wallet.quorum, 3);
+
+    // Two clerks don't reach the threshold.
+    let tx = ProposeTransfer::sign(
+        ALICE_NAME.to_string(),
+        BOB_NAME.to_string(),
+        10,
+        0,
+        Height(0),
+        &clerk1_pk,
+        &clerk1_sk,
+    );
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    let proposal_id = tx.hash();
+
+    let tx = ApproveTransfer::sign(&proposal_id, &clerk2_pk, &clerk2_sk);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 100);
+
+    // The CFO completes the threshold.
+    let tx = ApproveTransfer::sign(&proposal_id, &cfo_pk, &cfo_sk);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 90);
+    let wallet = api.get_wallet(BOB_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 110);
+
+    // The CFO and a clerk add another owner with a weight of 2.
+    let (new_pk, _) = crypto::gen_keypair();
+    let tx = AddOwner::sign(ALICE_NAME.to_string(), &new_pk, 2, 0, &cfo_pk, &cfo_sk);
+    api.transfer(&tx);
+    let tx = AddOwner::sign(ALICE_NAME.to_string(), &new_pk, 2, 0, &clerk1_pk, &clerk1_sk);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.pub_keys, vec![cfo_pk, clerk1_pk, clerk2_pk, new_pk]);
+    assert_eq!(wallet.weights, vec![2, 1, 1, 2]);
+}
+
+/// Check that the quorum required for a transfer depends on its amount.
+#[test]
+fn test_quorum_tiers() {
+    let (mut testkit, api) = create_testkit();
+    let keys = (0..3).map(|_| crypto::gen_keypair()).collect::<Vec<_>>();
+    let pub_keys = keys.iter().map(|(pk, _)| *pk).collect::<Vec<_>>();
+    let tiers = vec![
+        QuorumTier { max_amount: 10, quorum: 1 },
+        QuorumTier { max_amount: 50, quorum: 2 },
+    ];
+
+    let tx_bad_tiers = CreateWallet::sign_weighted(
+        ALICE_NAME,
+        pub_keys.clone(),
+        Vec::new(),
+        3,
+        tiers.iter().rev().cloned().collect(),
+        &keys[0].0,
+        &keys[0].1,
+    );
+    api.transfer(&tx_bad_tiers);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx_bad_tiers.hash(),
+        &json!({ "type": "error", "code": 16, "description": "Quorum tiers are not correct" }),
+    );
+
+    let tx_alice = CreateWallet::sign_weighted(
+        ALICE_NAME,
+        pub_keys.clone(),
+        Vec::new(),
+        3,
+        tiers.clone(),
+        &keys[0].0,
+        &keys[0].1,
+    );
+    api.transfer(&tx_alice);
+    let (tx_bob, _, _) = api.create_wallet(BOB_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.quorum_tiers, tiers);
+
+    // Each transfer is approved by one more owner than the last one.
+    for (amount, approvals) in &[(5, 1), (30, 2), (60, 3)] {
+        let balance = api.get_wallet(ALICE_NAME.to_string()).unwrap().balance;
+        for (i, (pk, sk)) in keys.iter().enumerate().take(*approvals) {
+            let tx = Transfer::sign(
+                ALICE_NAME.to_string(),
+                BOB_NAME.to_string(),
+                *amount,
+                0,
+                pk,
+                sk,
+            );
+            api.transfer(&tx);
+            testkit.create_block();
+            api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+            let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+            if i + 1 < *approvals {
+                assert_eq!(wallet.balance, balance);
+            } else {
+                assert_eq!(wallet.balance, balance - amount);
+            }
+        }
+    }
+
+    // Without the tiers every transfer requires the full quorum.
+    for (pk, sk) in &keys {
+        let tx = ChangeQuorumTiers::sign(ALICE_NAME.to_string(), Vec::new(), 0, pk, sk);
+        api.transfer(&tx);
+    }
+    testkit.create_block();
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert!(wallet.quorum_tiers.is_empty());
+
+    let tx = Transfer::sign(
+        ALICE_NAME.to_string(),
+        BOB_NAME.to_string(),
+        5,
+        1,
+        &keys[0].0,
+        &keys[0].1,
+    );
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 5);
+}
+
+/// Check that transfers within the spending limit need only the lower quorum.
+#[test]
+fn test_spending_limit() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 3, 3);
+    let (tx_bob, _, _) = api.create_wallet(BOB_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+
+    // Allow any single owner to send up to 50 units per 5 blocks.
+    for (pk, sk) in pubkeys_alice.iter().zip(&keys_alice) {
+        let tx = SetSpendingLimit::sign(ALICE_NAME.to_string(), 50, 5, 1, 0, pk, sk);
+        api.transfer(&tx);
+    }
+    testkit.create_block();
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.history_len, 2);
+
+    let transfer = |amount, seed| {
+        Transfer::sign(
+            ALICE_NAME.to_string(),
+            BOB_NAME.to_string(),
+            amount,
+            seed,
+            &pubkeys_alice[0],
+            &keys_alice[0],
+        )
+    };
+
+    let tx = transfer(30, 0);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 70);
+
+    // A transfer exceeding the limit keeps the approval until the full quorum is reached.
+    let tx = transfer(30, 1);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 70);
+
+    for (pk, sk) in pubkeys_alice.iter().zip(&keys_alice).skip(1) {
+        let tx = Transfer::sign(ALICE_NAME.to_string(), BOB_NAME.to_string(), 30, 1, pk, sk);
+        api.transfer(&tx);
+    }
+    testkit.create_block();
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 40);
+
+    // The full quorum can also propose more than the limit.
+    let tx = ProposeTransfer::sign(
+        ALICE_NAME.to_string(),
+        BOB_NAME.to_string(),
+        30,
+        0,
+        Height(0),
+        &pubkeys_alice[0],
+        &keys_alice[0],
+    );
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    let proposal_id = tx.hash();
+    for (pk, sk) in pubkeys_alice.iter().zip(&keys_alice).skip(1) {
+        let tx = ApproveTransfer::sign(&proposal_id, pk, sk);
+        api.transfer(&tx);
+    }
+    testkit.create_block();
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 10);
+
+    // The limit is available again once the earlier transfers leave the window.
+    for _ in 0..5 {
+        testkit.create_block();
+    }
+    let tx = transfer(10, 2);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 0);
+}
+
+/// Check that spendings never leave a window which reaches past the maximum height.
+#[test]
+fn test_spending_limit_unbounded_window() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 2, 2);
+    let (tx_bob, _, _) = api.create_wallet(BOB_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+
+    for (pk, sk) in pubkeys_alice.iter().zip(&keys_alice) {
+        let tx = SetSpendingLimit::sign(ALICE_NAME.to_string(), 50, u64::max_value(), 1, 0, pk, sk);
+        api.transfer(&tx);
+    }
+    testkit.create_block();
+
+    let transfer = |amount, seed| {
+        Transfer::sign(
+            ALICE_NAME.to_string(),
+            BOB_NAME.to_string(),
+            amount,
+            seed,
+            &pubkeys_alice[0],
+            &keys_alice[0],
+        )
+    };
+    let tx = transfer(30, 0);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 70);
+
+    // The earlier transfer still counts toward the limit.
+    testkit.create_blocks_until(Height(testkit.height().0 + 10));
+    let tx = transfer(30, 1);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 70);
+}
+
+/// Check that an approved time-locked transfer reserves the funds until its execution height.
+#[test]
+fn test_scheduled_transfer() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 2, 2);
+    let (tx_bob, _, _) = api.create_wallet(BOB_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+
+    let execute_at = Height(testkit.height().0 + 4);
+    let tx_propose = ProposeTransfer {
+        from: ALICE_NAME.to_string(),
+        to: BOB_NAME.to_string(),
+        amount: 10,
+        seed: 0,
+        valid_until: Height(0),
+        execute_at,
+        memo: String::new(),
+        asset: String::new(),
+    }
+    .into_signed(&pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx_propose);
+    testkit.create_block();
+    api.assert_tx_status(tx_propose.hash(), &json!({ "type": "success" }));
+
+    let tx = ApproveTransfer::sign(&tx_propose.hash(), &pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    // The amount is reserved, but not transferred yet.
+    let (pending, _) = api.get_proposal(tx_propose.hash());
+    assert_eq!(pending.unwrap().status, ProposalStatus::Scheduled);
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 90);
+    let wallet = api.get_wallet(BOB_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 100);
+
+    let tx = RejectTransfer::sign(&tx_propose.hash(), &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 9, "description": "Transfer proposal is not pending" }),
+    );
+
+    testkit.create_blocks_until(Height(execute_at.0 - 1));
+    let wallet = api.get_wallet(BOB_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 100);
+
+    testkit.create_block();
+    let (pending, archived) = api.get_proposal(tx_propose.hash());
+    assert!(pending.is_none());
+    assert_eq!(archived.unwrap().status, ProposalStatus::Executed);
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 90);
+    let wallet = api.get_wallet(BOB_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 110);
+    assert_eq!(wallet.history_len, 2);
+
+    // The execution is recorded as a provable wallet event of the receiver.
+    let events = api.get_wallet_events(BOB_NAME);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].kind, WalletEventKind::ScheduledTransferExecuted);
+    assert_eq!(events[0].id, tx_propose.hash());
+    assert_eq!(events[0].height, execute_at);
+    assert_eq!(events[0].amount, 10);
+    assert!(Schema::new(testkit.snapshot()).scheduled_proposals(execute_at).is_empty());
+}
+
+/// Check that the owners can cancel a scheduled transfer before its execution height.
+#[test]
+fn test_cancel_scheduled_transfer() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 2, 2);
+    let (tx_bob, _, _) = api.create_wallet(BOB_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+
+    let tx_propose = ProposeTransfer {
+        from: ALICE_NAME.to_string(),
+        to: BOB_NAME.to_string(),
+        amount: 10,
+        seed: 0,
+        valid_until: Height(0),
+        execute_at: Height(testkit.height().0 + 10),
+        memo: String::new(),
+        asset: String::new(),
+    }
+    .into_signed(&pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx_propose);
+    testkit.create_block();
+    api.assert_tx_status(tx_propose.hash(), &json!({ "type": "success" }));
+
+    let tx = ApproveTransfer::sign(&tx_propose.hash(), &pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 90);
+
+    // A single owner can't cancel the transfer.
+    let tx = CancelTransfer::sign(&tx_propose.hash(), &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    let (pending, _) = api.get_proposal(tx_propose.hash());
+    assert_eq!(pending.unwrap().status, ProposalStatus::Scheduled);
+
+    let tx = CancelTransfer::sign(&tx_propose.hash(), &pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    let (pending, archived) = api.get_proposal(tx_propose.hash());
+    assert!(pending.is_none());
+    assert_eq!(archived.unwrap().status, ProposalStatus::Cancelled);
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 100);
+    assert_eq!(wallet.history_len, 3);
+
+    testkit.create_blocks_until(Height(testkit.height().0 + 10));
+    let wallet = api.get_wallet(BOB_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 100);
+}
+
+/// Check that the spender's wallet transfers funds from the owner's wallet within
+/// the allowance approved by the quorum of the owner's wallet.
+#[test]
+fn test_allowance() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 2, 2);
+    let (tx_bob, pubkeys_bob, keys_bob) = api.create_wallet(BOB_NAME, 1, 1);
+    let (tx_carol, _, _) = api.create_wallet(CAROL_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_carol.hash(), &json!({ "type": "success" }));
+
+    for i in 0..2 {
+        assert!(api.get_allowance(ALICE_NAME, BOB_NAME).is_none());
+        let tx = Approve::sign(ALICE_NAME.to_string(), BOB_NAME.to_string(), 50, 0, &pubkeys_alice[i], &keys_alice[i]);
+        api.transfer(&tx);
+        testkit.create_block();
+        api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    }
+    assert_eq!(api.get_allowance(ALICE_NAME, BOB_NAME), Some(Allowance::new(ALICE_NAME, BOB_NAME, 50)));
+    assert_eq!(api.get_wallet(ALICE_NAME.to_string()).unwrap().history_len, 2);
+
+    // Spending from the allowance counts toward the spending limit of Alice's wallet.
+    for (pk, sk) in pubkeys_alice.iter().zip(&keys_alice) {
+        let tx = SetSpendingLimit::sign(ALICE_NAME.to_string(), 50, 5, 1, 0, pk, sk);
+        api.transfer(&tx);
+    }
+    testkit.create_block();
+
+    // Bob's key spends from Alice's wallet without her quorum.
+    let tx = TransferFrom::sign(
+        ALICE_NAME.to_string(),
+        BOB_NAME.to_string(),
+        CAROL_NAME.to_string(),
+        30,
+        0,
+        &pubkeys_bob[0],
+        &keys_bob[0],
+    );
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    assert_eq!(api.get_wallet(ALICE_NAME.to_string()).unwrap().balance, 70);
+    assert_eq!(api.get_wallet(CAROL_NAME.to_string()).unwrap().balance, 130);
+    assert_eq!(api.get_allowance(ALICE_NAME, BOB_NAME).unwrap().amount, 20);
+    assert_eq!(api.get_wallet(ALICE_NAME.to_string()).unwrap().history_len, 4);
+
+    let tx = Transfer::sign(
+        ALICE_NAME.to_string(),
+        CAROL_NAME.to_string(),
+        30,
+        0,
+        &pubkeys_alice[0],
+        &keys_alice[0],
+    );
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    assert_eq!(api.get_wallet(ALICE_NAME.to_string()).unwrap().balance, 70);
+
+    // Alice's keys can't use Bob's allowance.
+    let tx = TransferFrom::sign(
+        ALICE_NAME.to_string(),
+        BOB_NAME.to_string(),
+        CAROL_NAME.to_string(),
+        10,
+        1,
+        &pubkeys_alice[0],
+        &keys_alice[0],
+    );
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "error", "code": 1, "description": "" }));
+
+    let tx = TransferFrom::sign(
+        ALICE_NAME.to_string(),
+        BOB_NAME.to_string(),
+        CAROL_NAME.to_string(),
+        30,
+        2,
+        &pubkeys_bob[0],
+        &keys_bob[0],
+    );
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 25, "description": "Allowance exceeded" }),
+    );
+    assert_eq!(api.get_wallet(ALICE_NAME.to_string()).unwrap().balance, 70);
+}
+
+/// Check that the memo is part of the approved transfer and is returned in the wallet history.
+#[test]
+fn test_transfer_memo() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 2, 2);
+    let (tx_bob, _, _) = api.create_wallet(BOB_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+
+    // Transfers with different memos are different proposals, so neither reaches the quorum.
+    let tx = Transfer::sign_with_memo(
+        ALICE_NAME.to_string(),
+        BOB_NAME.to_string(),
+        10,
+        0,
+        "INV-1".to_string(),
+        &pubkeys_alice[0],
+        &keys_alice[0],
+    );
+    api.transfer(&tx);
+    let tx = Transfer::sign_with_memo(
+        ALICE_NAME.to_string(),
+        BOB_NAME.to_string(),
+        10,
+        0,
+        "INV-2".to_string(),
+        &pubkeys_alice[1],
+        &keys_alice[1],
+    );
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    assert_eq!(api.get_wallet(BOB_NAME.to_string()).unwrap().balance, 100);
+
+    let tx_propose = ProposeTransfer {
+        from: ALICE_NAME.to_string(),
+        to: BOB_NAME.to_string(),
+        amount: 20,
+        seed: 1,
+        valid_until: Height(0),
+        execute_at: Height(0),
+        memo: "INV-3".to_string(),
+        asset: String::new(),
+    }
+    .into_signed(&pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx_propose);
+    testkit.create_block();
+    api.assert_tx_status(tx_propose.hash(), &json!({ "type": "success" }));
+    let (pending, _) = api.get_proposal(tx_propose.hash());
+    assert_eq!(pending.unwrap().memo, "INV-3");
+
+    let tx = ApproveTransfer::sign(&tx_propose.hash(), &pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    assert_eq!(api.get_wallet(BOB_NAME.to_string()).unwrap().balance, 120);
+
+    // The history of the receiver holds the approval which executed the transfer.
+    let history = api.get_wallet_history(BOB_NAME);
+    assert_eq!(history.transactions.len(), 2);
+    assert_eq!(history.memos, vec![String::new(), "INV-3".to_string()]);
+
+    let tx = Transfer::sign_with_memo(
+        ALICE_NAME.to_string(),
+        BOB_NAME.to_string(),
+        10,
+        2,
+        "x".repeat(257),
+        &pubkeys_alice[0],
+        &keys_alice[0],
+    );
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 29, "description": "Memo is too long" }),
+    );
+}
+
+/// Check that the invoice is paid once by a transfer proposal filled from the invoice.
+#[test]
+fn test_invoice() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 2, 2);
+    let (tx_bob, pubkeys_bob, keys_bob) = api.create_wallet(BOB_NAME, 1, 1);
+    let (tx_carol, pubkeys_carol, keys_carol) = api.create_wallet(CAROL_NAME, 2, 2);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_carol.hash(), &json!({ "type": "success" }));
+
+    let tx_invoice = CreateInvoice::sign(
+        BOB_NAME.to_string(),
+        30,
+        "INV-7".to_string(),
+        Height(0),
+        0,
+        &pubkeys_bob[0],
+        &keys_bob[0],
+    );
+    api.transfer(&tx_invoice);
+    testkit.create_block();
+    api.assert_tx_status(tx_invoice.hash(), &json!({ "type": "success" }));
+    let invoices = api.get_open_invoices(BOB_NAME);
+    assert_eq!(invoices.len(), 1);
+    assert_eq!(invoices[0].id, tx_invoice.hash());
+    assert_eq!(invoices[0].invoice.memo, "INV-7");
+
+    let tx_pay = PayInvoice::sign(&tx_invoice.hash(), ALICE_NAME.to_string(), &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx_pay);
+    let tx_other = PayInvoice::sign(&tx_invoice.hash(), CAROL_NAME.to_string(), &pubkeys_carol[0], &keys_carol[0]);
+    api.transfer(&tx_other);
+    testkit.create_block();
+    api.assert_tx_status(tx_pay.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_other.hash(), &json!({ "type": "success" }));
+    let (pending, _) = api.get_proposal(tx_pay.hash());
+    let pending = pending.unwrap();
+    assert_eq!((pending.amount, pending.memo.as_str()), (30, "INV-7"));
+    assert_eq!(api.get_open_invoices(BOB_NAME).len(), 1);
+
+    let tx = ApproveTransfer::sign(&tx_pay.hash(), &pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    assert_eq!(api.get_wallet(ALICE_NAME.to_string()).unwrap().balance, 70);
+    assert_eq!(api.get_wallet(BOB_NAME.to_string()).unwrap().balance, 130);
+    assert!(api.get_open_invoices(BOB_NAME).is_empty());
+    let snapshot = testkit.snapshot();
+    assert!(Schema::new(&snapshot).payee_invoices(&crypto::hash(BOB_NAME.as_bytes())).is_empty());
+
+    // The other proposal to pay the invoice is cancelled.
+    let (pending, archived) = api.get_proposal(tx_other.hash());
+    assert!(pending.is_none());
+    assert_eq!(archived.unwrap().status, ProposalStatus::Cancelled);
+
+    let tx = PayInvoice::sign(&tx_invoice.hash(), ALICE_NAME.to_string(), &pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 32, "description": "Invoice is already paid" }),
+    );
+
+    // Invoices can't be paid after their expiry height.
+    let tx_invoice = CreateInvoice::sign(
+        BOB_NAME.to_string(),
+        10,
+        "INV-8".to_string(),
+        Height(testkit.height().0 + 2),
+        1,
+        &pubkeys_bob[0],
+        &keys_bob[0],
+    );
+    api.transfer(&tx_invoice);
+    testkit.create_block();
+    api.assert_tx_status(tx_invoice.hash(), &json!({ "type": "success" }));
+    assert_eq!(api.get_open_invoices(BOB_NAME).len(), 1);
+
+    testkit.create_block();
+    assert!(api.get_open_invoices(BOB_NAME).is_empty());
+    let tx = PayInvoice::sign(&tx_invoice.hash(), ALICE_NAME.to_string(), &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 31, "description": "Invoice has expired" }),
+    );
+}
+
+/// Check that assets are issued by their issuer's wallet and transferred separately
+/// from the native currency.
+#[test]
+fn test_assets() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 1, 1);
+    let (tx_bob, pubkeys_bob, keys_bob) = api.create_wallet(BOB_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+
+    let tx = CreateAsset::sign("USD".to_string(), ALICE_NAME.to_string(), &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    let tx = CreateAsset::sign("USD".to_string(), BOB_NAME.to_string(), &pubkeys_bob[0], &keys_bob[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 33, "description": "Asset already exists" }),
+    );
+
+    // Only the owners of the issuer's wallet can issue the asset.
+    let issue = Issue {
+        to: ALICE_NAME.to_string(),
+        amount: 50,
+        seed: 0,
+        asset: "USD".to_string(),
+    };
+    let tx = issue.clone().into_signed(&pubkeys_bob[0], &keys_bob[0]);
+    api.transfer(&tx);
+    let tx_issue = issue.into_signed(&pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx_issue);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 11, "description": "Issuer is not authorized" }),
+    );
+    api.assert_tx_status(tx_issue.hash(), &json!({ "type": "success" }));
+    assert_eq!(api.get_asset("USD").unwrap().supply, 50);
+    assert_eq!(api.get_asset_balance(ALICE_NAME, "USD"), 50);
+
+    let transfer = Transfer {
+        from: ALICE_NAME.to_string(),
+        to: BOB_NAME.to_string(),
+        amount: 20,
+        seed: 0,
+        memo: String::new(),
+        asset: "USD".to_string(),
+    };
+    let tx = transfer.clone().into_signed(&pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    assert_eq!(api.get_asset_balance(ALICE_NAME, "USD"), 30);
+    assert_eq!(api.get_asset_balance(BOB_NAME, "USD"), 20);
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 100);
+    assert_eq!(wallet.history_len, 3);
+    assert_eq!(api.get_wallet(BOB_NAME.to_string()).unwrap().balance, 100);
+
+    let tx = Transfer { amount: 40, seed: 1, ..transfer.clone() }.into_signed(&pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 3, "description": "Insufficient currency amount" }),
+    );
+
+    let tx = Transfer { asset: "EUR".to_string(), ..transfer }.into_signed(&pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 34, "description": "Asset doesn't exist" }),
+    );
+}
+
+/// Check that a transfer of an asset which also pays a fee is recorded once in the
+/// history of the sender's wallet.
+#[test]
+fn test_asset_transfer_fee() {
+    let mut fixed = BTreeMap::new();
+    fixed.insert("Transfer".to_string(), 2);
+    let (mut testkit, api) = create_testkit_with_service(Service::new(ServiceConfig {
+        treasury: Some(CAROL_NAME.to_string()),
+        fees: FeeSchedule {
+            fixed,
+            transfer_rate: 0,
+        },
+        ..ServiceConfig::default()
+    }));
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 1, 1);
+    let (tx_bob, _, _) = api.create_wallet(BOB_NAME, 1, 1);
+    let (tx_carol, _, _) = api.create_wallet(CAROL_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_carol.hash(), &json!({ "type": "success" }));
+
+    let tx = CreateAsset::sign("USD".to_string(), ALICE_NAME.to_string(), &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    let tx = Issue {
+        to: ALICE_NAME.to_string(),
+        amount: 50,
+        seed: 0,
+        asset: "USD".to_string(),
+    }
+    .into_signed(&pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    let tx = Transfer {
+        from: ALICE_NAME.to_string(),
+        to: BOB_NAME.to_string(),
+        amount: 20,
+        seed: 0,
+        memo: String::new(),
+        asset: "USD".to_string(),
+    }
+    .into_signed(&pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    assert_eq!(api.get_asset_balance(ALICE_NAME, "USD"), 30);
+    assert_eq!(api.get_asset_balance(BOB_NAME, "USD"), 20);
+    assert_eq!(api.get_fee(tx.hash()), 2);
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 98);
+    assert_eq!(wallet.history_len, 3);
+    let history = api.get_wallet_history(ALICE_NAME);
+    assert_eq!(history.transactions.len(), 3);
+}
+
+/// Check that the asset is issued by the quorum of its issuer's wallet and that its
+/// supply can't overflow.
+#[test]
+fn test_asset_issue_quorum() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 2, 2);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+
+    for i in 0..2 {
+        let tx = CreateAsset::sign("USD".to_string(), ALICE_NAME.to_string(), &pubkeys_alice[i], &keys_alice[i]);
+        api.transfer(&tx);
+        testkit.create_block();
+        api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    }
+
+    let issue = Issue {
+        to: ALICE_NAME.to_string(),
+        amount: 50,
+        seed: 0,
+        asset: "USD".to_string(),
+    };
+    let tx = issue.clone().into_signed(&pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    assert_eq!(api.get_asset("USD").unwrap().supply, 0);
+
+    let tx = issue.into_signed(&pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    assert_eq!(api.get_asset("USD").unwrap().supply, 50);
+    assert_eq!(api.get_asset_balance(ALICE_NAME, "USD"), 50);
+
+    let issue = Issue {
+        to: ALICE_NAME.to_string(),
+        amount: u64::max_value(),
+        seed: 1,
+        asset: "USD".to_string(),
+    };
+    let tx = issue.clone().into_signed(&pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    let tx = issue.into_signed(&pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 37, "description": "Total supply overflow" }),
+    );
+    assert_eq!(api.get_asset("USD").unwrap().supply, 50);
+    assert_eq!(api.get_asset_balance(ALICE_NAME, "USD"), 50);
+}
+
+/// Check that the service pays the subscription every period and skips the payments
+/// which exceed the balance of the payer's wallet.
+#[test]
+fn test_subscription() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 2, 2);
+    let (tx_bob, _, _) = api.create_wallet(BOB_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+
+    let subscription = CreateSubscription {
+        from: ALICE_NAME.to_string(),
+        to: BOB_NAME.to_string(),
+        amount: 40,
+        period_blocks: 2,
+        count: 3,
+        seed: 0,
+    };
+    for i in 0..2 {
+        let tx = subscription.clone().into_signed(&pubkeys_alice[i], &keys_alice[i]);
+        api.transfer(&tx);
+        testkit.create_block();
+        api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    }
+    let created_at = testkit.height();
+
+    testkit.create_blocks_until(Height(created_at.0 + 1));
+    assert_eq!(api.get_wallet(ALICE_NAME.to_string()).unwrap().balance, 100);
+
+    testkit.create_blocks_until(Height(created_at.0 + 2));
+    assert_eq!(api.get_wallet(ALICE_NAME.to_string()).unwrap().balance, 60);
+    assert_eq!(api.get_wallet(BOB_NAME.to_string()).unwrap().balance, 140);
+
+    testkit.create_blocks_until(Height(created_at.0 + 4));
+    assert_eq!(api.get_wallet(ALICE_NAME.to_string()).unwrap().balance, 20);
+    assert_eq!(api.get_wallet(BOB_NAME.to_string()).unwrap().balance, 180);
+
+    // The last payment exceeds the balance, so it is skipped and recorded in the history.
+    testkit.create_blocks_until(Height(created_at.0 + 8));
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 20);
+    assert_eq!(wallet.history_len, 4);
+    let wallet = api.get_wallet(BOB_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 180);
+    assert_eq!(wallet.history_len, 3);
+
+    // Payments and skipped payments are recorded as provable wallet events.
+    let events = api.get_wallet_events(ALICE_NAME);
+    assert_eq!(events.len(), 3);
+    assert!(events.iter().all(|event| event.id == subscription.hash()));
+    let count = |kind| events.iter().filter(|event| event.kind == kind).count();
+    assert_eq!(count(WalletEventKind::SubscriptionPaid), 2);
+    assert_eq!(count(WalletEventKind::SubscriptionSkipped), 1);
+    let events = api.get_wallet_events(BOB_NAME);
+    assert_eq!(events.len(), 2);
+    assert!(events.iter().all(|event| event.amount == 40));
+
+    // All payments have been made, so there is nothing to cancel.
+    let tx = CancelSubscription::sign(&subscription.hash(), &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 28, "description": "Subscription doesn't exist" }),
+    );
+}
+
+/// Check that the subscription is cancelled by the payee alone, but not by a stranger.
+#[test]
+fn test_cancel_subscription() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 1, 1);
+    let (tx_bob, pubkeys_bob, keys_bob) = api.create_wallet(BOB_NAME, 1, 1);
+    let (tx_carol, pubkeys_carol, keys_carol) = api.create_wallet(CAROL_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_carol.hash(), &json!({ "type": "success" }));
+
+    let subscription = CreateSubscription {
+        from: ALICE_NAME.to_string(),
+        to: BOB_NAME.to_string(),
+        amount: 10,
+        period_blocks: 2,
+        count: 5,
+        seed: 0,
+    };
+    let tx = subscription.clone().into_signed(&pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    let tx = CancelSubscription::sign(&subscription.hash(), &pubkeys_carol[0], &keys_carol[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "error", "code": 1, "description": "" }));
+
+    testkit.create_block();
+    assert_eq!(api.get_wallet(BOB_NAME.to_string()).unwrap().balance, 110);
+
+    let next_payment = Schema::new(testkit.snapshot())
+        .subscription(&subscription.hash())
+        .unwrap()
+        .next_payment;
+    let tx = CancelSubscription::sign(&subscription.hash(), &pubkeys_bob[0], &keys_bob[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    assert!(Schema::new(testkit.snapshot()).due_subscriptions(next_payment).is_empty());
+
+    testkit.create_blocks_until(Height(testkit.height().0 + 6));
+    assert_eq!(api.get_wallet(ALICE_NAME.to_string()).unwrap().balance, 90);
+    assert_eq!(api.get_wallet(BOB_NAME.to_string()).unwrap().balance, 110);
+}
+
+/// Check that a subscription with the last payment past the maximum height is rejected.
+#[test]
+fn test_subscription_height_overflow() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 1, 1);
+    let (tx_bob, _, _) = api.create_wallet(BOB_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+
+    // Periods and numbers of payments for which the last payment height overflows.
+    let cases = [(u64::max_value(), 1), (u64::max_value() / 2, 3)];
+    for (seed, &(period_blocks, count)) in cases.iter().enumerate() {
+        let tx = CreateSubscription {
+            from: ALICE_NAME.to_string(),
+            to: BOB_NAME.to_string(),
+            amount: 10,
+            period_blocks,
+            count,
+            seed: seed as u64,
+        }
+        .into_signed(&pubkeys_alice[0], &keys_alice[0]);
+        api.transfer(&tx);
+        testkit.create_block();
+        api.assert_tx_status(
+            tx.hash(),
+            &json!({ "type": "error", "code": 26, "description": "Subscription is not correct" }),
+        );
+    }
+    assert_eq!(api.get_wallet(ALICE_NAME.to_string()).unwrap().balance, 100);
+}
+
+/// Check that locked funds are claimed with the preimage of the hashlock.
+#[test]
+fn test_htlc_claim() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 2, 2);
+    let (tx_bob, pubkeys_bob, keys_bob) = api.create_wallet(BOB_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+
+    let preimage = b"secret";
+    let lock = HtlcLock {
+        from: ALICE_NAME.to_string(),
+        to: BOB_NAME.to_string(),
+        amount: 30,
+        hashlock: crypto::hash(preimage),
+        timeout_height: Height(testkit.height().0 + 10),
+        seed: 0,
+    };
+    let id = lock.hash();
+
+    // The funds are locked once both owners of Alice's wallet submit the lock.
+    let tx = lock.clone().into_signed(&pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    assert!(api.get_htlc(id).is_none());
+
+    let tx = lock.into_signed(&pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    assert_eq!(api.get_htlc(id).unwrap().status, HtlcStatus::Locked);
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 70);
+
+    let tx = HtlcClaim::sign(&id, b"wrong", &pubkeys_bob[0], &keys_bob[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 23, "description": "Preimage doesn't match the hashlock" }),
+    );
+
+    let tx = HtlcClaim::sign(&id, preimage, &pubkeys_bob[0], &keys_bob[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    // The revealed preimage is available to the counterparty.
+    let htlc = api.get_htlc(id).unwrap();
+    assert_eq!(htlc.status, HtlcStatus::Claimed);
+    assert_eq!(htlc.preimage, preimage.to_vec());
+    let wallet = api.get_wallet(BOB_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 130);
+    assert_eq!(wallet.history_len, 2);
+
+    let tx = HtlcRefund::sign(&id, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 20, "description": "Lock is already claimed or refunded" }),
+    );
+}
+
+/// Check that locked funds are refunded to the sender once the lock times out.
+#[test]
+fn test_htlc_refund() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 1, 1);
+    let (tx_bob, pubkeys_bob, keys_bob) = api.create_wallet(BOB_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+
+    let preimage = b"secret";
+    let timeout_height = Height(testkit.height().0 + 2);
+    let lock = HtlcLock {
+        from: ALICE_NAME.to_string(),
+        to: BOB_NAME.to_string(),
+        amount: 30,
+        hashlock: crypto::hash(preimage),
+        timeout_height,
+        seed: 0,
+    };
+    let id = lock.hash();
+    let tx = lock.into_signed(&pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    let tx = HtlcRefund::sign(&id, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 22, "description": "Lock has not timed out yet" }),
+    );
+
+    let tx = HtlcClaim::sign(&id, preimage, &pubkeys_bob[0], &keys_bob[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 21, "description": "Lock has timed out" }),
+    );
+
+    // Anyone can return the funds to the sender once the lock has timed out.
+    let tx = HtlcRefund::sign(&id, &pubkeys_bob[0], &keys_bob[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    assert_eq!(api.get_htlc(id).unwrap().status, HtlcStatus::Refunded);
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 100);
+    assert_eq!(wallet.history_len, 3);
+    let wallet = api.get_wallet(BOB_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 100);
+}
+
+/// Check that approving an unknown proposal fails as expected.
+#[test]
+fn test_approve_nonexisting_proposal() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 2, 2);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+
+    let tx = ApproveTransfer::sign(&crypto::hash(&[]), &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 7, "description": "Transfer proposal doesn't exist" }),
+    );
+}
+
+/// Check that a transfer proposal is dropped once the quorum can no longer be reached.
+#[test]
+fn test_reject_transfer() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 2, 2);
+    let (tx_bob, _, _) = api.create_wallet(BOB_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+
+    let tx_propose = ProposeTransfer::sign(
+        ALICE_NAME.to_string(),
+        BOB_NAME.to_string(),
+        10,        // transferred amount
+        0,         // seed
+        Height(0), // default lifetime
+        &pubkeys_alice[0],
+        &keys_alice[0],
+    );
+    api.transfer(&tx_propose);
+    testkit.create_block();
+    api.assert_tx_status(tx_propose.hash(), &json!({ "type": "success" }));
+
+    let tx = RejectTransfer::sign(&tx_propose.hash(), &pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    // With one of two owners against, the proposal is rejected.
+    let tx = ApproveTransfer::sign(&tx_propose.hash(), &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 9, "description": "Transfer proposal is not pending" }),
+    );
+
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 100);
+    let wallet = api.get_wallet(BOB_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 100);
+}
+
+/// Check that fees are moved to the treasury wallet and recorded for the transactions.
+#[test]
+fn test_fees() {
+    let mut fixed = BTreeMap::new();
+    fixed.insert("CreateWallet".to_string(), 5);
+    fixed.insert("Transfer".to_string(), 2);
+    let (mut testkit, api) = create_testkit_with_service(Service::new(ServiceConfig {
+        treasury: Some(CAROL_NAME.to_string()),
+        fees: FeeSchedule {
+            fixed,
+            transfer_rate: 1000,
+        },
+        ..ServiceConfig::default()
+    }));
+
+    // Fees can't be charged until the treasury wallet is created.
+    let (tx_alice, _, _) = api.create_wallet(ALICE_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx_alice.hash(),
+        &json!({ "type": "error", "code": 38, "description": "Treasury wallet doesn't exist" }),
+    );
+
+    // The treasury wallet doesn't pay fees to itself.
+    let (tx_carol, _, _) = api.create_wallet(CAROL_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_carol.hash(), &json!({ "type": "success" }));
+
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 1, 1);
+    let (tx_bob, _, _) = api.create_wallet(BOB_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 95);
+    let wallet = api.get_wallet(CAROL_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 110);
+
+    // The fixed fee and 10% of the amount are charged for the transfer.
+    let tx = Transfer::sign(ALICE_NAME.to_string(), BOB_NAME.to_string(), 50, 0, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 38);
+    let wallet = api.get_wallet(BOB_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 145);
+    let wallet = api.get_wallet(CAROL_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 117);
+    assert_eq!(api.get_fee(tx.hash()), 7);
+
+    let history = api.get_wallet_history(ALICE_NAME);
+    assert_eq!(history.fees, vec![5, 7]);
+    let history = api.get_wallet_history(CAROL_NAME);
+    assert_eq!(history.fees, vec![0, 5, 5, 7]);
+
+    // The amount fits into the balance, but the fee for it doesn't.
+    let tx = Transfer::sign(ALICE_NAME.to_string(), BOB_NAME.to_string(), 36, 1, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(
+        tx.hash(),
+        &json!({ "type": "error", "code": 35, "description": "Insufficient funds to pay the fee" }),
+    );
+
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 38);
+    assert_eq!(api.get_fee(tx.hash()), 0);
+}
+
+/// Check that the fixed fee for a batch transfer is charged once, when it is executed.
+#[test]
+fn test_batch_transfer_fee() {
+    let mut fixed = BTreeMap::new();
+    fixed.insert("BatchTransfer".to_string(), 3);
+    let (mut testkit, api) = create_testkit_with_service(Service::new(ServiceConfig {
+        treasury: Some(CAROL_NAME.to_string()),
+        fees: FeeSchedule {
+            fixed,
+            transfer_rate: 0,
+        },
+        ..ServiceConfig::default()
+    }));
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 2, 2);
+    let (tx_bob, _, _) = api.create_wallet(BOB_NAME, 1, 1);
+    let (tx_carol, _, _) = api.create_wallet(CAROL_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_carol.hash(), &json!({ "type": "success" }));
+
+    let outputs = vec![TransferOutput::new(BOB_NAME, 10)];
+    let tx = BatchTransfer::sign(ALICE_NAME.to_string(), outputs.clone(), 0, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    assert_eq!(api.get_wallet(ALICE_NAME.to_string()).unwrap().balance, 100);
+    assert_eq!(api.get_fee(tx.hash()), 0);
+
+    let tx = BatchTransfer::sign(ALICE_NAME.to_string(), outputs, 0, &pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    assert_eq!(api.get_fee(tx.hash()), 3);
+
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 87);
+    let wallet = api.get_wallet(BOB_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 110);
+    let wallet = api.get_wallet(CAROL_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 103);
+}
+
+/// Check that fixed fees can only be set for the transactions which are charged with them.
+#[test]
+#[should_panic(expected = "Fixed fee is set for unsupported transaction Issue")]
+fn test_fees_unsupported_transaction() {
+    let mut fixed = BTreeMap::new();
+    fixed.insert("Issue".to_string(), 1);
+    create_testkit_with_service(Service::new(ServiceConfig {
+        fees: FeeSchedule {
+            fixed,
+            transfer_rate: 0,
+        },
+        ..ServiceConfig::default()
+    }));
+}
+
+/// Check that the genesis wallets are created with their balances, and new wallets
+/// get the configured initial balance.
+#[test]
+fn test_genesis() {
+    let (carol_pk, carol_sk) = crypto::gen_keypair();
+    let (mut testkit, api) = create_testkit_with_service(Service::with_genesis(GenesisConfig {
+        wallets: vec![GenesisWallet {
+            name: CAROL_NAME.to_string(),
+            pub_keys: vec![carol_pk],
+            weights: vec![],
+            quorum: 1,
+            balance: 1000,
+        }],
+        config: ServiceConfig {
+            initial_balance: 0,
+            ..ServiceConfig::default()
+        },
+    }));
+
+    let wallet = api.get_wallet(CAROL_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 1000);
+    assert_eq!(api.get_total_supply(), 1000);
+
+    let (tx_alice, _, _) = api.create_wallet(ALICE_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 0);
+    assert_eq!(api.get_total_supply(), 1000);
+
+    let tx = Transfer::sign(CAROL_NAME.to_string(), ALICE_NAME.to_string(), 10, 0, &carol_pk, &carol_sk);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 10);
+    let wallet = api.get_wallet(CAROL_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 990);
+
+    // The genesis record of the wallet is an event rather than a transaction.
+    let history = api.get_wallet_history(CAROL_NAME);
+    assert_eq!(history.transactions.len(), 1);
+    let events = api.get_wallet_events(CAROL_NAME);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].kind, WalletEventKind::GenesisWalletCreated);
+    assert_eq!(events[0].id, crypto::hash(CAROL_NAME.as_bytes()));
+    assert_eq!(events[0].amount, 1000);
+}
+
+/// Check that the names of the genesis wallets can't exceed the maximum length.
+#[test]
+#[should_panic(expected = "Name of genesis wallet Carol is too long")]
+fn test_genesis_name_too_long() {
+    let (carol_pk, _) = crypto::gen_keypair();
+    create_testkit_with_service(Service::with_genesis(GenesisConfig {
+        wallets: vec![GenesisWallet {
+            name: CAROL_NAME.to_string(),
+            pub_keys: vec![carol_pk],
+            weights: vec![],
+            quorum: 1,
+            balance: 1000,
+        }],
+        config: ServiceConfig {
+            max_name_length: 3,
+            ..ServiceConfig::default()
+        },
+    }));
+}
+
+/// Check that the total balance of the genesis wallets can't exceed the maximum supply.
+#[test]
+#[should_panic(expected = "Total balance of genesis wallets exceeds the maximum supply")]
+fn test_genesis_max_supply() {
+    let (alice_pk, _) = crypto::gen_keypair();
+    let (carol_pk, _) = crypto::gen_keypair();
+    create_testkit_with_service(Service::with_genesis(GenesisConfig {
+        wallets: vec![
+            GenesisWallet {
+                name: ALICE_NAME.to_string(),
+                pub_keys: vec![alice_pk],
+                weights: vec![],
+                quorum: 1,
+                balance: 600,
+            },
+            GenesisWallet {
+                name: CAROL_NAME.to_string(),
+                pub_keys: vec![carol_pk],
+                weights: vec![],
+                quorum: 1,
+                balance: 600,
+            },
+        ],
+        config: ServiceConfig {
+            max_supply: Some(1000),
+            ..ServiceConfig::default()
+        },
+    }));
+}
+
+/// Check that the service parameters can be changed through the configuration service.
+#[test]
+fn test_config_change() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, _, _) = api.create_wallet(ALICE_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+
+    let proposal = {
+        let mut cfg = testkit.configuration_change_proposal();
+        cfg.set_service_config(
+            "cryptocurrency",
+            ServiceConfig {
+                initial_balance: 10,
+                max_name_length: 3,
+                ..ServiceConfig::default()
+            },
+        );
+        cfg.set_actual_from(testkit.height().next().next());
+        cfg
+    };
+    testkit.commit_configuration_change(proposal);
+    testkit.create_block();
+
+    let (tx_bob, _, _) = api.create_wallet(BOB_NAME, 1, 1);
+    let (tx_carol, _, _) = api.create_wallet(CAROL_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(
+        tx_carol.hash(),
+        &json!({ "type": "error", "code": 36, "description": "Name is too long" }),
+    );
+
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 100);
+    let wallet = api.get_wallet(BOB_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 10);
+}
+
+/// Check that an invalid configuration of the service is skipped in favour of the
+/// previous one.
+#[test]
+fn test_invalid_config_change() {
+    let (mut testkit, api) = create_testkit_with_service(Service::new(ServiceConfig {
+        initial_balance: 10,
+        ..ServiceConfig::default()
+    }));
+
+    let proposal = {
+        let mut cfg = testkit.configuration_change_proposal();
+        cfg.set_service_config("cryptocurrency", json!({ "initial_balance": "ten" }));
+        cfg.set_actual_from(testkit.height().next().next());
+        cfg
+    };
+    testkit.commit_configuration_change(proposal);
+    testkit.create_block();
+
+    let (tx_alice, _, _) = api.create_wallet(ALICE_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 10);
+}
+
+/// Check that the wallets can be looked up by the keys of their owners.
+#[test]
+fn test_wallets_by_key() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 2, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+
+    // The second owner of Alice's wallet also co-owns Bob's wallet.
+    let (bob_pk, bob_sk) = crypto::gen_keypair();
+    let tx_bob = CreateWallet::sign(BOB_NAME, vec![bob_pk, pubkeys_alice[1]], 1, &bob_pk, &bob_sk);
+    api.transfer(&tx_bob);
+    testkit.create_block();
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+
+    assert_eq!(api.get_wallets_by_key(pubkeys_alice[0]), vec![ALICE_NAME.to_string()]);
+    assert_eq!(
+        api.get_wallets_by_key(pubkeys_alice[1]),
+        vec![ALICE_NAME.to_string(), BOB_NAME.to_string()]
+    );
+    assert_eq!(api.get_wallets_by_key(bob_pk), vec![BOB_NAME.to_string()]);
+
+    let tx = RemoveOwner::sign(ALICE_NAME.to_string(), &pubkeys_alice[1], 0, &pubkeys_alice[0], &keys_alice[0]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    assert_eq!(api.get_wallets_by_key(pubkeys_alice[1]), vec![BOB_NAME.to_string()]);
+
+    let (new_pk, _) = crypto::gen_keypair();
+    assert!(api.get_wallets_by_key(new_pk).is_empty());
+
+    let tx = ReplaceOwner::sign(BOB_NAME.to_string(), &pubkeys_alice[1], &new_pk, 0, &bob_pk, &bob_sk);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    assert!(api.get_wallets_by_key(pubkeys_alice[1]).is_empty());
+    assert_eq!(api.get_wallets_by_key(new_pk), vec![BOB_NAME.to_string()]);
+}
+
+/// Check listing the pending transfer proposals of a wallet and the proposals awaiting
+/// the signature of a key.
+#[test]
+fn test_pending_proposals() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 3, 2);
+    let (tx_bob, _, _) = api.create_wallet(BOB_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+    assert!(api.get_pending_proposals(ALICE_NAME).is_empty());
+
+    let tx_first = ProposeTransfer::sign(
+        ALICE_NAME.to_string(),
+        BOB_NAME.to_string(),
+        10,
+        0,
+        Height(0),
+        &pubkeys_alice[0],
+        &keys_alice[0],
+    );
+    let tx_second = ProposeTransfer::sign(
+        ALICE_NAME.to_string(),
+        BOB_NAME.to_string(),
+        20,
+        1,
+        Height(0),
+        &pubkeys_alice[0],
+        &keys_alice[0],
+    );
+    api.transfer(&tx_first);
+    testkit.create_block();
+    api.transfer(&tx_second);
+    testkit.create_block();
+    api.assert_tx_status(tx_first.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_second.hash(), &json!({ "type": "success" }));
+
+    let pending = api.get_pending_proposals(ALICE_NAME);
+    assert_eq!(pending.len(), 2);
+    assert_eq!(pending[0].id, tx_first.hash());
+    assert_eq!(pending[0].proposal.to, BOB_NAME);
+    assert_eq!(pending[0].proposal.amount, 10);
+    assert_eq!(pending[0].proposal.approvals, vec![pubkeys_alice[0]]);
+    assert_eq!(pending[0].remaining_quorum, 1);
+    assert_eq!(pending[1].id, tx_second.hash());
+    assert_eq!(pending[1].proposal.amount, 20);
+    assert!(api.get_pending_proposals(BOB_NAME).is_empty());
+
+    // The proposer has already approved both proposals.
+    assert_eq!(api.get_awaiting_proposals(pubkeys_alice[0]), vec![(ALICE_NAME.to_string(), vec![])]);
+    assert_eq!(
+        api.get_awaiting_proposals(pubkeys_alice[1]),
+        vec![(ALICE_NAME.to_string(), vec![tx_first.hash(), tx_second.hash()])]
+    );
+
+    // A rejected proposal no longer awaits the signature of the key, but is still pending.
+    let tx = RejectTransfer::sign(&tx_first.hash(), &pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    assert_eq!(
+        api.get_awaiting_proposals(pubkeys_alice[1]),
+        vec![(ALICE_NAME.to_string(), vec![tx_second.hash()])]
+    );
+    assert_eq!(api.get_pending_proposals(ALICE_NAME).len(), 2);
+
+    // Executed proposals are no longer pending.
+    let tx = ApproveTransfer::sign(&tx_second.hash(), &pubkeys_alice[2], &keys_alice[2]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    let pending = api.get_pending_proposals(ALICE_NAME);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].id, tx_first.hash());
+    assert_eq!(pending[0].proposal.rejections, vec![pubkeys_alice[1]]);
+    assert_eq!(
+        api.get_awaiting_proposals(pubkeys_alice[2]),
+        vec![(ALICE_NAME.to_string(), vec![tx_first.hash()])]
+    );
+
+    // Neither are proposals rejected by the owners.
+    let tx = RejectTransfer::sign(&tx_first.hash(), &pubkeys_alice[2], &keys_alice[2]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    assert!(api.get_pending_proposals(ALICE_NAME).is_empty());
+    assert_eq!(api.get_awaiting_proposals(pubkeys_alice[1]), vec![(ALICE_NAME.to_string(), vec![])]);
+}
+
+/// Check that the remaining quorum of the pending proposals within the spending limit
+/// counts the lower quorum of the limit.
+#[test]
+fn test_pending_proposals_spending_limit() {
+    let (mut testkit, api) = create_testkit();
+    let (tx_alice, pubkeys_alice, keys_alice) = api.create_wallet(ALICE_NAME, 3, 3);
+    let (tx_bob, _, _) = api.create_wallet(BOB_NAME, 1, 1);
+    testkit.create_block();
+    api.assert_tx_status(tx_alice.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_bob.hash(), &json!({ "type": "success" }));
+
+    // Allow any two owners to send up to 50 units per 5 blocks.
+    for (pk, sk) in pubkeys_alice.iter().zip(&keys_alice) {
+        let tx = SetSpendingLimit::sign(ALICE_NAME.to_string(), 50, 5, 2, 0, pk, sk);
+        api.transfer(&tx);
+    }
+    testkit.create_block();
+
+    let propose = |amount, seed| {
+        ProposeTransfer::sign(
+            ALICE_NAME.to_string(),
+            BOB_NAME.to_string(),
+            amount,
+            seed,
+            Height(0),
+            &pubkeys_alice[0],
+            &keys_alice[0],
+        )
+    };
+    let tx_within = propose(30, 0);
+    let tx_over = propose(60, 1);
+    api.transfer(&tx_within);
+    api.transfer(&tx_over);
+    testkit.create_block();
+    api.assert_tx_status(tx_within.hash(), &json!({ "type": "success" }));
+    api.assert_tx_status(tx_over.hash(), &json!({ "type": "success" }));
+
+    let pending = api.get_pending_proposals(ALICE_NAME);
+    assert_eq!(pending.len(), 2);
+    let remaining_quorum = |id| {
+        pending
+            .iter()
+            .find(|pending| pending.id == id)
+            .map(|pending| pending.remaining_quorum)
+    };
+    assert_eq!(remaining_quorum(tx_within.hash()), Some(1));
+    assert_eq!(remaining_quorum(tx_over.hash()), Some(2));
+
+    // The second approval executes the proposal within the limit.
+    let tx = ApproveTransfer::sign(&tx_within.hash(), &pubkeys_alice[1], &keys_alice[1]);
+    api.transfer(&tx);
+    testkit.create_block();
+    api.assert_tx_status(tx.hash(), &json!({ "type": "success" }));
+    let wallet = api.get_wallet(ALICE_NAME.to_string()).unwrap();
+    assert_eq!(wallet.balance, 70);
+}
+
+/// Wrapper for the cryptocurrency service API allowing to easily use it
+/// (compared to `TestKitApi` calls).
+struct CryptocurrencyApi {
+    pub inner: TestKitApi,
+}
+
+impl CryptocurrencyApi {
+    /// Generates a wallet creation transaction with a random key pair, sends it over HTTP,
+    /// and checks the synchronous result (i.e., the hash of the transaction returned
+    /// within the response).
+    /// Note that the transaction is not immediately added to the blockchain, but rather is put
+    /// to the pool of unconfirmed transactions.
+    fn create_wallet(&self, name: &str, key_num: u32, quorum: u32) -> (Signed<RawTransaction>, Vec<PublicKey>, Vec<SecretKey>) {
+        if quorum > key_num {
+            panic!("Qurum should less or equal to key_num");
+        }
+
+        let mut pub_keys: Vec<PublicKey> = Vec::with_capacity(key_num as usize);
+        let mut keys : Vec<SecretKey> = Vec::with_capacity(key_num as usize);
+        for _ in 0..key_num {
+            let (pubkey, key) = crypto::gen_keypair();
+            pub_keys.push(pubkey);
+            keys.push(key);
+        }
+
+        // Create a pre-signed transaction
+        let tx = CreateWallet::sign(name, pub_keys.clone(), quorum,&pub_keys[0], &keys[0]);
+
+        let data = messages::to_hex_string(&tx);
+        let tx_info: TransactionResponse = self
+            .inner
+            .public(ApiKind::Explorer)
+            .query(&json!({ "tx_body": data }))
+            .post("v1/transactions")
+            .unwrap();
+        assert_eq!(tx_info.tx_hash, tx.hash());
+        (tx, pub_keys, keys)
+    }
+
+    fn get_wallet(&self, name: String) -> Option<Wallet> {
+        let wallet_info = self
+            .inner
+            .public(ApiKind::Service("cryptocurrency"))
+            .query(&WalletQuery { name: name.clone() })
+            .get::<WalletInfo>("v1/wallets/info")
+            .unwrap();
+
+        let to_wallet = wallet_info.wallet_proof.to_wallet.check().unwrap();
+        let wallet = to_wallet
+            .all_entries()
+            .find(|(ref k, _)| **k == crypto::hash(name.as_bytes()))
+            .and_then(|tuple| tuple.1)
+            .cloned();
+        wallet
+    }
+
+    /// Returns the names of the wallets owned by the given key, checking that the names
+    /// and the wallets are anchored in the state hash of the latest block.
+    fn get_wallets_by_key(&self, pub_key: PublicKey) -> Vec<String> {
+        let info = self
+            .inner
+            .public(ApiKind::Service("cryptocurrency"))
+            .query(&KeyQuery { pub_key })
+            .get::<KeyWalletsInfo>("v1/wallets/by-key")
+            .unwrap();
+
+        let state_hash = *info.block_proof.block.state_hash();
+        let proof = info.key_wallets_proof;
+        let to_table = proof.to_table.check().unwrap();
+        assert_eq!(to_table.merkle_root(), state_hash);
+        let to_names = proof.to_names.check().unwrap();
+        assert_eq!(to_table.entries().next().unwrap().1, &to_names.merkle_root());
+
+        let to_table = info.wallet_proof.to_table.check().unwrap();
+        assert_eq!(to_table.merkle_root(), state_hash);
+        let to_wallet = info.wallet_proof.to_wallet.check().unwrap();
+        assert_eq!(to_table.entries().next().unwrap().1, &to_wallet.merkle_root());
+        let wallets = to_wallet.entries().map(|(_, wallet)| wallet.clone()).collect::<Vec<_>>();
+
+        let names = match (to_names.entries().next(), proof.names) {
+            (Some((_, names_hash)), Some(names)) => names
+                .validate(*names_hash, wallets.len() as u64)
+                .unwrap()
+                .into_iter()
+                .map(|(_, name)| name.clone())
+                .collect::<Vec<_>>(),
+            (None, None) => Vec::new(),
+            _ => panic!("Inconsistent proof of the wallet names"),
+        };
+        assert_eq!(names.len(), wallets.len());
+        assert!(wallets
+            .iter()
+            .all(|wallet| wallet.pub_keys.contains(&pub_key) && names.contains(&wallet.name)));
+        names
+    }
+
+    /// Returns the pending transfer proposals of the wallet with the given name.
+    fn get_pending_proposals(&self, wallet: &str) -> Vec<PendingProposal> {
+        let info = self
+            .inner
+            .public(ApiKind::Service("cryptocurrency"))
+            .query(&PendingProposalsQuery { wallet: wallet.to_string() })
+            .get::<PendingProposalsInfo>("v1/proposals/pending")
+            .unwrap();
+
+        let state_hash = *info.block_proof.block.state_hash();
+        check_wallet_proposals(state_hash, info.wallet_proposals)
+    }
+
+    /// Returns the ids of the transfer proposals awaiting the signature of the key,
+    /// grouped by the names of the wallets owned by the key.
+    fn get_awaiting_proposals(&self, pub_key: PublicKey) -> Vec<(String, Vec<Hash>)> {
+        let info = self
+            .inner
+            .public(ApiKind::Service("cryptocurrency"))
+            .query(&KeyQuery { pub_key })
+            .get::<AwaitingProposalsInfo>("v1/proposals/awaiting")
+            .unwrap();
+
+        let state_hash = *info.block_proof.block.state_hash();
+        let to_table = info.key_wallets_proof.to_table.check().unwrap();
+        assert_eq!(to_table.merkle_root(), state_hash);
+        let to_names = info.key_wallets_proof.to_names.check().unwrap();
+        assert_eq!(to_table.entries().next().unwrap().1, &to_names.merkle_root());
+        assert_eq!(to_names.entries().count(), info.key_wallets_proof.names.iter().count());
+
+        info.wallets
+            .into_iter()
+            .map(|wallet| {
+                let name = wallet.wallet.clone();
+                let proposals = check_wallet_proposals(state_hash, wallet);
+                assert!(proposals
+                    .iter()
+                    .all(|pending| !pending.proposal.is_approved_by(&pub_key)
+                        && !pending.proposal.is_rejected_by(&pub_key)));
+                (name, proposals.into_iter().map(|pending| pending.id).collect())
+            })
+            .collect()
+    }
+
+    /// Returns the history of the wallet with the given name.
+    fn get_wallet_history(&self, name: &str) -> WalletHistory {
+        let wallet_info = self
+            .inner
+            .public(ApiKind::Service("cryptocurrency"))
+            .query(&WalletQuery { name: name.to_string() })
+            .get::<WalletInfo>("v1/wallets/info")
+            .unwrap();
+        wallet_info.wallet_history.unwrap()
+    }
+
+    /// Returns the events recorded in the history of the wallet with the given name,
+    /// checking that they are anchored in the state hash of the latest block.
+    fn get_wallet_events(&self, name: &str) -> Vec<WalletEvent> {
+        let wallet_info = self
+            .inner
+            .public(ApiKind::Service("cryptocurrency"))
+            .query(&WalletQuery { name: name.to_string() })
+            .get::<WalletInfo>("v1/wallets/info")
+            .unwrap();
+
+        let state_hash = *wallet_info.block_proof.block.state_hash();
+        let events = wallet_info.wallet_history.unwrap().events;
+        let to_table = events.to_table.check().unwrap();
+        assert_eq!(to_table.merkle_root(), state_hash);
+        let to_events = events.to_events.check().unwrap();
+        assert_eq!(to_table.entries().next().unwrap().1, &to_events.merkle_root());
+        to_events.entries().map(|(_, event)| event.clone()).collect()
+    }
+
+    /// Returns the transfer proposal with the given id from the pending and archived tables,
+    /// checking that both tables are anchored in the state hash of the latest block.
+    fn get_proposal(&self, id: Hash) -> (Option<TransferProposal>, Option<TransferProposal>) {
+        let proposal_info = self
+            .inner
+            .public(ApiKind::Service("cryptocurrency"))
+            .query(&ProposalQuery { id })
+            .get::<ProposalInfo>("v1/proposals/info")
+            .unwrap();
+
+        let state_hash = *proposal_info.block_proof.block.state_hash();
+        let check = |proof: ProposalProof| {
+            let to_table = proof.to_table.check().unwrap();
+            assert_eq!(to_table.merkle_root(), state_hash);
+            let to_proposal = proof.to_proposal.check().unwrap();
+            assert_eq!(to_table.entries().next().unwrap().1, &to_proposal.merkle_root());
+            let proposal = to_proposal
+                .all_entries()
+                .find(|(ref k, _)| **k == id)
+                .and_then(|tuple| tuple.1)
+                .cloned();
+            proposal
+        };
+        (
+            check(proposal_info.pending_proof),
+            check(proposal_info.archive_proof),
+        )
+    }
+
+    /// Returns the hash time-locked contract with the given id, checking that it is anchored
+    /// in the state hash of the latest block.
+    fn get_htlc(&self, id: Hash) -> Option<Htlc> {
+        let htlc_info = self
+            .inner
+            .public(ApiKind::Service("cryptocurrency"))
+            .query(&HtlcQuery { id })
+            .get::<HtlcInfo>("v1/htlcs/info")
+            .unwrap();
+
+        let to_table = htlc_info.htlc_proof.to_table.check().unwrap();
+        assert_eq!(to_table.merkle_root(), *htlc_info.block_proof.block.state_hash());
+        let to_htlc = htlc_info.htlc_proof.to_htlc.check().unwrap();
+        assert_eq!(to_table.entries().next().unwrap().1, &to_htlc.merkle_root());
+        let htlc = to_htlc
+            .all_entries()
+            .find(|(ref k, _)| **k == id)
+            .and_then(|tuple| tuple.1)
+            .cloned();
+        htlc
+    }
+
+    /// Returns the allowance of the spender's wallet on the owner's wallet, checking that
+    /// it is anchored in the state hash of the latest block.
+    fn get_allowance(&self, owner: &str, spender: &str) -> Option<Allowance> {
+        let allowance_info = self
+            .inner
+            .public(ApiKind::Service("cryptocurrency"))
+            .query(&AllowanceQuery {
+                owner: owner.to_string(),
+                spender: spender.to_string(),
+            })
+            .get::<AllowanceInfo>("v1/allowances/info")
+            .unwrap();
+
+        let to_table = allowance_info.allowance_proof.to_table.check().unwrap();
+        assert_eq!(to_table.merkle_root(), *allowance_info.block_proof.block.state_hash());
+        let to_allowance = allowance_info.allowance_proof.to_allowance.check().unwrap();
+        assert_eq!(to_table.entries().next().unwrap().1, &to_allowance.merkle_root());
+        let key = allowance_key(owner, spender);
+        let allowance = to_allowance
+            .all_entries()
+            .find(|(ref k, _)| **k == key)
+            .and_then(|tuple| tuple.1)
+            .cloned();
+        allowance
+    }
+
+    /// Returns the asset with the given name, checking that it is anchored in the state hash
+    /// of the latest block.
+    fn get_asset(&self, name: &str) -> Option<Asset> {
+        let asset_info = self
+            .inner
+            .public(ApiKind::Service("cryptocurrency"))
+            .query(&AssetQuery { name: name.to_string() })
+            .get::<AssetInfo>("v1/assets/info")
+            .unwrap();
+
+        let to_table = asset_info.asset_proof.to_table.check().unwrap();
+        assert_eq!(to_table.merkle_root(), *asset_info.block_proof.block.state_hash());
+        let to_asset = asset_info.asset_proof.to_asset.check().unwrap();
+        assert_eq!(to_table.entries().next().unwrap().1, &to_asset.merkle_root());
+        let key = crypto::hash(name.as_bytes());
+        let asset = to_asset
+            .all_entries()
+            .find(|(ref k, _)| **k == key)
+            .and_then(|tuple| tuple.1)
+            .cloned();
+        asset
+    }
+
+    /// Returns the balance of the wallet in the asset, checking that it is anchored in
+    /// the state hash of the latest block.
+    fn get_asset_balance(&self, wallet: &str, asset: &str) -> u64 {
+        let balance_info = self
+            .inner
+            .public(ApiKind::Service("cryptocurrency"))
+            .query(&BalanceQuery {
+                wallet: wallet.to_string(),
+                asset: asset.to_string(),
+            })
+            .get::<BalanceInfo>("v1/assets/balance")
+            .unwrap();
+
+        let to_table = balance_info.balance_proof.to_table.check().unwrap();
+        assert_eq!(to_table.merkle_root(), *balance_info.block_proof.block.state_hash());
+        let to_balance = balance_info.balance_proof.to_balance.check().unwrap();
+        assert_eq!(to_table.entries().next().unwrap().1, &to_balance.merkle_root());
+        let key = asset_balance_key(wallet, asset);
+        let balance = to_balance
+            .all_entries()
+            .find(|(ref k, _)| **k == key)
+            .and_then(|tuple| tuple.1)
+            .cloned();
+        balance.unwrap_or_default()
+    }
+
+    /// Returns the fee paid for the transaction, checking that it is anchored in the state hash
+    /// of the latest block.
+    fn get_fee(&self, transaction: Hash) -> u64 {
+        let fee_info = self
+            .inner
+            .public(ApiKind::Service("cryptocurrency"))
+            .query(&FeeQuery { transaction })
+            .get::<FeeInfo>("v1/fees/info")
+            .unwrap();
+
+        let to_table = fee_info.fee_proof.to_table.check().unwrap();
+        assert_eq!(to_table.merkle_root(), *fee_info.block_proof.block.state_hash());
+        let to_fee = fee_info.fee_proof.to_fee.check().unwrap();
+        assert_eq!(to_table.entries().next().unwrap().1, &to_fee.merkle_root());
+        let fee = to_fee
+            .all_entries()
+            .find(|(ref k, _)| **k == transaction)
+            .and_then(|tuple| tuple.1)
+            .cloned();
+        fee.unwrap_or_default()
+    }
+
+    /// Returns the invoices of the payee's wallet which can still be paid.
+    fn get_open_invoices(&self, payee: &str) -> Vec<OpenInvoice> {
+        self.inner
+            .public(ApiKind::Service("cryptocurrency"))
+            .query(&InvoicesQuery { payee: payee.to_string() })
+            .get::<Vec<OpenInvoice>>("v1/invoices/open")
+            .unwrap()
+    }
+
+    /// Returns the total supply, checking that it is anchored in the state hash
+    /// of the latest block.
+    fn get_total_supply(&self) -> u64 {
+        let supply_info = self
+            .inner
+            .public(ApiKind::Service("cryptocurrency"))
+            .get::<SupplyInfo>("v1/supply")
+            .unwrap();
+
+        let to_table = supply_info.to_table.check().unwrap();
+        assert_eq!(to_table.merkle_root(), *supply_info.block_proof.block.state_hash());
+        let entry_hash = if supply_info.total_supply == 0 {
+            Hash::zero()
+        } else {
+            supply_info.total_supply.hash()
+        };
+        assert_eq!(to_table.entries().next().unwrap().1, &entry_hash);
+        supply_info.total_supply
+    }
+
+    /// Sends a transfer transaction over HTTP and checks the synchronous result.
+    fn transfer(&self, tx: &Signed<RawTransaction>) {
+        let data = messages::to_hex_string(&tx);
+        let tx_info: TransactionResponse = self
+            .inner
+            .public(ApiKind::Explorer)
+            .query(&json!({ "tx_body": data }))
+            .post("v1/transactions")
+            .unwrap();
+        assert_eq!(tx_info.tx_hash, tx.hash());
+    }
+
+    /// Asserts that a wallet with the specified public key is not known to the blockchain.
+    fn assert_no_wallet(&self, name: String) {
+        let wallet_info: WalletInfo = self
+            .inner
+            .public(ApiKind::Service("cryptocurrency"))
+            .query(&WalletQuery { name: name.clone() })
+            .get("v1/wallets/info")
+            .unwrap();
+
+        let to_wallet = wallet_info.wallet_proof.to_wallet.check().unwrap();
+        assert!(to_wallet.missing_keys().find(|v| **v == crypto::hash(name.as_bytes())).is_some())
+    }
+
+    /// Asserts that the transaction with the given hash has a specified status.
+    fn assert_tx_status(&self, tx_hash: Hash, expected_status: &serde_json::Value) {
+        let info: serde_json::Value = self
+            .inner
+            .public(ApiKind::Explorer)
+            .query(&TransactionQuery::new(tx_hash))
+            .get("v1/transactions")
+            .unwrap();
+
+        if let serde_json::Value::Object(mut info) = info {
+            let tx_status = info.remove("status").unwrap();
+            assert_eq!(tx_status, *expected_status);
+        } else {
+            panic!("Invalid transaction info format, object expected");
+        }
+    }
+}
+
+/// Checks the proofs of the pending transfer proposals of a wallet against the state hash
+/// and returns the proposals.
+fn check_wallet_proposals(state_hash: Hash, wallet: WalletProposals) -> Vec<PendingProposal> {
+    let to_table = wallet.ids_proof.to_table.check().unwrap();
+    assert_eq!(to_table.merkle_root(), state_hash);
+    let to_ids = wallet.ids_proof.to_ids.check().unwrap();
+    assert_eq!(to_table.entries().next().unwrap().1, &to_ids.merkle_root());
+
+    let to_table = wallet.proposal_proof.to_table.check().unwrap();
+    assert_eq!(to_table.merkle_root(), state_hash);
+    let to_proposal = wallet.proposal_proof.to_proposal.check().unwrap();
+    assert_eq!(to_table.entries().next().unwrap().1, &to_proposal.merkle_root());
+    let proven = to_proposal.entries().count() as u64;
+
+    let ids = match (to_ids.entries().next(), wallet.ids_proof.ids) {
+        (Some((_, ids_hash)), Some(ids)) => ids
+            .validate(*ids_hash, proven)
+            .unwrap()
+            .into_iter()
+            .map(|(_, id)| *id)
+            .collect::<Vec<_>>(),
+        (None, None) => Vec::new(),
+        _ => panic!("Inconsistent proof of the proposal ids"),
+    };
+    for pending in &wallet.proposals {
+        assert!(ids.contains(&pending.id));
+        assert!(to_proposal
+            .entries()
+            .any(|(id, proposal)| *id == pending.id && proposal.hash() == pending.proposal.hash()));
+    }
+    wallet.proposals
+}
+
+/// Creates a testkit together with the API wrapper defined above.
+fn create_testkit() -> (TestKit, CryptocurrencyApi) {
+    create_testkit_with_service(Service::default())
+}
+
+/// Creates a testkit with the given service instance together with the API wrapper.
+fn create_testkit_with_service(service: Service) -> (TestKit, CryptocurrencyApi) {
+    let testkit = TestKitBuilder::validator().with_service(service).create();
     let api = CryptocurrencyApi {
         inner: testkit.api(),
     };
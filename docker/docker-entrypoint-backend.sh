@@ -2,8 +2,21 @@
 
 set -e
 
+# The genesis block of the cryptocurrency service can be configured with the
+# INITIAL_BALANCE, ISSUER_KEY and GENESIS_WALLETS environment variables.
+args=()
+if [ -n "$INITIAL_BALANCE" ]; then
+  args+=(--initial-balance "$INITIAL_BALANCE")
+fi
+if [ -n "$ISSUER_KEY" ]; then
+  args+=(--issuer-key "$ISSUER_KEY")
+fi
+if [ -n "$GENESIS_WALLETS" ]; then
+  args+=(--genesis-wallets "$GENESIS_WALLETS")
+fi
+
 if [ ! -d /data/validators ]; then
-  ./target/release/exonum-cryptocurrency-multisig  generate-testnet 1 --output-dir /data
+  ./target/release/exonum-cryptocurrency-multisig  generate-testnet 1 --output-dir /data "${args[@]}"
 fi
 
 exec "$@"